@@ -81,6 +81,10 @@ static QString errorToString(const int err)
             return QLatin1String("SVG doesn't have a valid size.");
         case RESVG_ERROR_PARSING_FAILED :
             return QLatin1String("Failed to parse an SVG data.");
+        case RESVG_ERROR_MISSING_FONTS :
+            return QLatin1String("No suitable fonts were found for a text.");
+        case RESVG_ERROR_UNKNOWN_ELEMENTS :
+            return QLatin1String("SVG has unknown elements.");
     }
 
     Q_UNREACHABLE();
@@ -234,6 +238,18 @@ public:
         resvg_options_set_keep_named_groups(d, keep);
     }
 
+    /**
+     * @brief Treats unknown SVG elements as errors.
+     *
+     * If set to `true`, parsing will fail instead of skipping unknown SVG elements.
+     *
+     * Default: false
+     */
+    void setRejectUnknownElements(const bool reject)
+    {
+        resvg_options_set_reject_unknown_elements(d, reject);
+    }
+
     /**
      * @brief Loads a font data into the internal fonts database.
      *
@@ -20,6 +20,8 @@ enum ErrorId {
     MalformedGZip,
     InvalidSize,
     ParsingFailed,
+    MissingFonts,
+    UnknownElements,
 }
 
 #[repr(C)]
@@ -224,6 +226,11 @@ pub extern "C" fn resvg_options_set_keep_named_groups(opt: *mut resvg_options, k
     cast_opt(opt).keep_named_groups = keep;
 }
 
+#[no_mangle]
+pub extern "C" fn resvg_options_set_reject_unknown_elements(opt: *mut resvg_options, reject: bool) {
+    cast_opt(opt).reject_unknown_elements = reject;
+}
+
 #[no_mangle]
 pub extern "C" fn resvg_options_load_system_fonts(opt: *mut resvg_options) {
     let opt = unsafe {
@@ -541,6 +548,8 @@ fn convert_error(e: usvg::Error) -> ErrorId {
         usvg::Error::MalformedGZip => ErrorId::MalformedGZip,
         usvg::Error::InvalidSize => ErrorId::InvalidSize,
         usvg::Error::ParsingFailed(_) => ErrorId::ParsingFailed,
+        usvg::Error::MissingFonts => ErrorId::MissingFonts,
+        usvg::Error::UnknownElements(_) => ErrorId::UnknownElements,
     }
 }
 
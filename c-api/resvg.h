@@ -69,6 +69,10 @@ typedef enum resvg_error {
     RESVG_ERROR_INVALID_SIZE,
     /** Failed to parse an SVG data. */
     RESVG_ERROR_PARSING_FAILED,
+    /** No suitable fonts were found for a text. */
+    RESVG_ERROR_MISSING_FONTS,
+    /** SVG has unknown elements. */
+    RESVG_ERROR_UNKNOWN_ELEMENTS,
 } resvg_error;
 
 /**
@@ -316,6 +320,16 @@ void resvg_options_set_image_rendering_mode(resvg_options *opt, resvg_image_rend
  */
 void resvg_options_set_keep_named_groups(resvg_options *opt, bool keep);
 
+/**
+ * @brief Treats unknown SVG elements as errors.
+ *
+ * If set to `true`, parsing will fail with `RESVG_ERROR_UNKNOWN_ELEMENTS`
+ * instead of skipping unknown SVG elements.
+ *
+ * Default: false
+ */
+void resvg_options_set_reject_unknown_elements(resvg_options *opt, bool reject);
+
 /**
  * @brief Loads a font data into the internal fonts database.
  *
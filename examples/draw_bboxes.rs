@@ -1,6 +1,4 @@
-use std::rc::Rc;
-
-use usvg::{NodeExt, SystemFontDB};
+use usvg::SystemFontDB;
 
 fn main() {
     let args: Vec<String> = std::env::args().collect();
@@ -27,29 +25,11 @@ fn main() {
 
     let rtree = usvg::Tree::from_file(&args[1], &opt).unwrap();
 
-    let mut bboxes = Vec::new();
-    for node in rtree.root().descendants() {
-        if !rtree.is_in_defs(&node) {
-            if let Some(bbox) = node.calculate_bbox() {
-                bboxes.push(bbox);
-            }
-        }
-    }
-
-    let stroke = Some(usvg::Stroke {
-        paint: usvg::Paint::Color(usvg::Color::new(255, 0, 0)),
-        opacity: 0.5.into(),
-        .. usvg::Stroke::default()
-    });
-
-    for bbox in bboxes {
-        rtree.root().append_kind(usvg::NodeKind::Path(usvg::Path {
-            stroke: stroke.clone(),
-            data: Rc::new(usvg::PathData::from_rect(bbox)),
-            .. usvg::Path::default()
-        }));
-    }
+    let render_opt = resvg::RenderOptions {
+        draw_bboxes: true,
+        .. resvg::RenderOptions::default()
+    };
 
-    let img = resvg::render(&rtree, fit_to, None).unwrap();
+    let img = resvg::render_with_options(&rtree, fit_to, None, &render_opt).unwrap();
     img.save_png(&args[2]).unwrap();
 }
@@ -0,0 +1,18 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+const MAX_PIXELS: u64 = 1000 * 1000;
+
+fuzz_target!(|data: &[u8]| {
+    let tree = match usvg::Tree::from_data(data, &usvg::Options::default()) {
+        Ok(tree) => tree,
+        Err(_) => return,
+    };
+
+    // `libfuzzer-sys` aborts on panic before it can be caught,
+    // so any panic inside the renderer will be reported as a crash.
+    let _ = resvg::try_render(
+        &tree, usvg::FitTo::Original, None, &resvg::RenderOptions::default(), MAX_PIXELS,
+    );
+});
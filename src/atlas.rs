@@ -0,0 +1,63 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::render::prelude::*;
+
+/// An SVG that should be rendered into an atlas.
+#[allow(missing_debug_implementations)]
+#[derive(Clone, Copy)]
+pub struct AtlasItem<'a> {
+    /// A tree to render.
+    pub tree: &'a usvg::Tree,
+
+    /// A size of the rendered tree.
+    pub fit_to: usvg::FitTo,
+
+    /// A horizontal position of the top-left corner in the atlas.
+    pub x: i32,
+
+    /// A vertical position of the top-left corner in the atlas.
+    pub y: i32,
+}
+
+/// Renders multiple SVGs into a single image.
+///
+/// Items are drawn in order, so the later ones will be on top when overlapping.
+/// Items outside the atlas are clipped.
+/// The `background` fills the whole atlas, while `opt` is applied to each item separately.
+///
+/// Returns an atlas image and a rectangle occupied by each item, in the same order.
+/// With `RenderOptions::flip_y`, the whole atlas is flipped, so the rectangles are flipped too.
+/// Returns `None` when the atlas or any of the items cannot be rendered.
+pub fn render_atlas(
+    items: &[AtlasItem],
+    size: ScreenSize,
+    background: Option<usvg::Color>,
+    opt: &RenderOptions,
+) -> Option<(crate::Image, Vec<ScreenRect>)> {
+    let (mut atlas, _) = crate::render::create_root_image(size, usvg::FitTo::Original, background)?;
+    atlas.reset_transform();
+
+    let mut rects = Vec::with_capacity(items.len());
+    for item in items {
+        let (view_box, tree_size) = crate::root_view_box(item.tree, opt);
+        let img = crate::render_root(&item.tree.root(), view_box, tree_size, item.fit_to, None, opt)?;
+
+        // Items are already flipped, so only their positions have to be flipped.
+        let y = if opt.flip_y {
+            size.height() as i32 - item.y - img.height() as i32
+        } else {
+            item.y
+        };
+
+        atlas.draw_surface(
+            &img, item.x as f32, y as f32, 255,
+            skia::BlendMode::SourceOver, skia::FilterQuality::None,
+        );
+
+        rects.push(ScreenRect::new(item.x, y, img.width(), img.height())?);
+    }
+
+    Some((crate::Image::from_surface(atlas), rects))
+}
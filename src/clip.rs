@@ -7,6 +7,7 @@ use crate::render::prelude::*;
 pub fn clip(
     node: &usvg::Node,
     cp: &usvg::ClipPath,
+    opt: &RenderOptions,
     bbox: Rect,
     layers: &mut Layers,
     canvas: &mut skia::Canvas,
@@ -29,10 +30,10 @@ pub fn clip(
 
         match *node.borrow() {
             usvg::NodeKind::Path(ref path_node) => {
-                crate::path::draw(&node.tree(), path_node, skia::BlendMode::Clear, &mut clip_surface);
+                crate::path::draw(&node.tree(), None, path_node, opt, skia::BlendMode::Clear, &mut clip_surface);
             }
             usvg::NodeKind::Group(ref g) => {
-                clip_group(&node, g, bbox, layers, &mut clip_surface);
+                clip_group(&node, g, opt, bbox, layers, &mut clip_surface);
             }
             _ => {}
         }
@@ -43,7 +44,7 @@ pub fn clip(
     if let Some(ref id) = cp.clip_path {
         if let Some(ref clip_node) = node.tree().defs_by_id(id) {
             if let usvg::NodeKind::ClipPath(ref cp) = *clip_node.borrow() {
-                clip(clip_node, cp, bbox, layers, canvas);
+                clip(clip_node, cp, opt, bbox, layers, canvas);
             }
         }
     }
@@ -54,9 +55,28 @@ pub fn clip(
     );
 }
 
+/// Clips the whole `surface` using a path in device coordinates.
+pub fn clip_surface(data: &usvg::PathData, surface: &mut skia::Surface) {
+    let size = try_opt!(ScreenSize::new(surface.width(), surface.height()));
+    let mut clip_surface = try_opt!(crate::render::create_subsurface(size));
+    clip_surface.fill(0, 0, 0, 255);
+
+    let mut paint = skia::Paint::new();
+    paint.set_style(skia::PaintStyle::Fill);
+    paint.set_anti_alias(true);
+    paint.set_blend_mode(skia::BlendMode::Clear);
+    clip_surface.draw_path(&crate::path::convert_path(data), &paint);
+
+    surface.reset_transform();
+    surface.draw_surface(
+        &clip_surface, 0.0, 0.0, 255, skia::BlendMode::DestinationOut, skia::FilterQuality::Low,
+    );
+}
+
 fn clip_group(
     node: &usvg::Node,
     g: &usvg::Group,
+    opt: &RenderOptions,
     bbox: Rect,
     layers: &mut Layers,
     canvas: &mut skia::Canvas,
@@ -73,8 +93,8 @@ fn clip_group(
 
                 clip_surface.set_transform(canvas.get_transform());
 
-                draw_group_child(&node, &mut clip_surface);
-                clip(clip_node, cp, bbox, layers, &mut clip_surface);
+                draw_group_child(&node, opt, &mut clip_surface);
+                clip(clip_node, cp, opt, bbox, layers, &mut clip_surface);
 
                 canvas.reset_transform();
                 canvas.draw_surface(
@@ -85,13 +105,13 @@ fn clip_group(
     }
 }
 
-fn draw_group_child(node: &usvg::Node, canvas: &mut skia::Canvas) {
+fn draw_group_child(node: &usvg::Node, opt: &RenderOptions, canvas: &mut skia::Canvas) {
     if let Some(child) = node.first_child() {
         canvas.concat(child.transform().to_native());
 
         match *child.borrow() {
             usvg::NodeKind::Path(ref path_node) => {
-                crate::path::draw(&child.tree(), path_node, skia::BlendMode::SourceOver, canvas);
+                crate::path::draw(&child.tree(), None, path_node, opt, skia::BlendMode::SourceOver, canvas);
             }
             _ => {}
         }
@@ -0,0 +1,89 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{Image, RenderOptions};
+
+/// An image difference summary.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct DiffStats {
+    /// The maximum difference of a single channel. In a 0..255 range.
+    pub max_diff: u8,
+
+    /// The mean difference of all channels. In a 0..255 range.
+    pub mean_diff: f64,
+
+    /// The percentage of pixels that are different.
+    pub diff_pixels: f64,
+}
+
+impl Image {
+    /// Compares two images.
+    ///
+    /// Returns an image with a per-channel absolute difference multiplied by `amplify`
+    /// and a difference summary. The difference image is opaque,
+    /// so identical images will produce a black one.
+    ///
+    /// Returns `None` when images have different sizes.
+    pub fn diff(&self, other: &Image, amplify: f64) -> Option<(Image, DiffStats)> {
+        if self.size() != other.size() {
+            return None;
+        }
+
+        let mut data = Vec::with_capacity(self.data.len());
+        let mut max_diff = 0;
+        let mut total_diff = 0u64;
+        let mut diff_pixels = 0u64;
+        for (c1, c2) in self.data.chunks(4).zip(other.data.chunks(4)) {
+            let mut d = [0u8; 4];
+            for i in 0..4 {
+                d[i] = (c1[i] as i16 - c2[i] as i16).unsigned_abs() as u8;
+                max_diff = std::cmp::max(max_diff, d[i]);
+                total_diff += d[i] as u64;
+            }
+
+            // Show an alpha difference as gray, since the difference image is opaque.
+            for v in &d[0..3] {
+                let v = std::cmp::max(*v, d[3]) as f64 * amplify;
+                data.push(v.min(255.0) as u8);
+            }
+            data.push(255);
+
+            if d != [0; 4] {
+                diff_pixels += 1;
+            }
+        }
+
+        let pixels_count = self.width() as u64 * self.height() as u64;
+        let stats = DiffStats {
+            max_diff,
+            mean_diff: total_diff as f64 / (pixels_count * 4) as f64,
+            diff_pixels: diff_pixels as f64 / pixels_count as f64 * 100.0,
+        };
+
+        let img = Image {
+            data,
+            width: self.width(),
+            height: self.height(),
+        };
+
+        Some((img, stats))
+    }
+}
+
+/// Renders two SVGs and compares the results.
+///
+/// See `Image::diff` for details.
+///
+/// Returns `None` when rendering failed or images have different sizes.
+pub fn render_diff(
+    tree1: &usvg::Tree,
+    tree2: &usvg::Tree,
+    fit_to: usvg::FitTo,
+    opt: &RenderOptions,
+    amplify: f64,
+) -> Option<(Image, DiffStats)> {
+    let img1 = crate::render_with_options(tree1, fit_to, None, opt)?;
+    let img2 = crate::render_with_options(tree2, fit_to, None, opt)?;
+    img1.diff(&img2, amplify)
+}
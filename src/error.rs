@@ -0,0 +1,42 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+/// List of all rendering errors.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RenderError {
+    /// An output image size is zero or cannot be represented.
+    InvalidSize,
+
+    /// An output image has more pixels than allowed.
+    ImageTooBig,
+
+    /// Failed to allocate an image.
+    AllocFailed,
+
+    /// Rendering has panicked.
+    ///
+    /// Indicates a bug in `resvg`.
+    Panicked,
+}
+
+impl std::fmt::Display for RenderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            RenderError::InvalidSize => {
+                write!(f, "output image has an invalid size")
+            }
+            RenderError::ImageTooBig => {
+                write!(f, "output image is too big")
+            }
+            RenderError::AllocFailed => {
+                write!(f, "failed to allocate an image")
+            }
+            RenderError::Panicked => {
+                write!(f, "rendering has panicked")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RenderError {}
@@ -230,6 +230,14 @@ impl Image {
 }
 
 
+/// Images used by the `BackgroundImage`, `FillPaint` and `StrokePaint` inputs.
+pub struct FilterExtraInputs<'a> {
+    pub background: Option<&'a skia::Surface>,
+    pub fill_paint: Option<&'a skia::Surface>,
+    pub stroke_paint: Option<&'a skia::Surface>,
+}
+
+
 struct FilterInputs<'a> {
     source: &'a skia::Surface,
     background: Option<&'a skia::Surface>,
@@ -249,20 +257,19 @@ pub fn apply(
     bbox: Option<Rect>,
     ts: &usvg::Transform,
     tree: &usvg::Tree,
-    background: Option<&skia::Surface>,
-    fill_paint: Option<&skia::Surface>,
-    stroke_paint: Option<&skia::Surface>,
+    opt: &RenderOptions,
+    extra_inputs: FilterExtraInputs,
     canvas: &mut skia::Surface,
 ) {
     let res = {
         let inputs = FilterInputs {
             source: canvas,
-            background,
-            fill_paint,
-            stroke_paint,
+            background: extra_inputs.background,
+            fill_paint: extra_inputs.fill_paint,
+            stroke_paint: extra_inputs.stroke_paint,
         };
 
-        _apply(filter, &inputs, bbox, ts, tree)
+        _apply(filter, &inputs, bbox, ts, tree, opt)
     };
 
     let res = res.and_then(|(image, region)| apply_to_canvas(image, region, canvas));
@@ -293,6 +300,7 @@ fn _apply(
     bbox: Option<Rect>,
     ts: &usvg::Transform,
     tree: &usvg::Tree,
+    opt: &RenderOptions,
 ) -> Result<(Image, ScreenRect), Error> {
     let mut results = Vec::new();
     let region = calc_region(filter, bbox, ts, inputs.source)?;
@@ -307,6 +315,10 @@ fn _apply(
                 let input2 = get_input(&fe.input2, region, inputs, &results)?;
                 apply_blend(fe, cs, region, input1, input2)
             }
+            usvg::FilterKind::FeDropShadow(ref fe) => {
+                let input = get_input(&fe.input, region, inputs, &results)?;
+                apply_drop_shadow(fe, filter.primitive_units, cs, bbox, ts, input)
+            }
             usvg::FilterKind::FeFlood(ref fe) => {
                 apply_flood(fe, region)
             }
@@ -331,7 +343,7 @@ fn _apply(
                 apply_tile(input, region)
             }
             usvg::FilterKind::FeImage(ref fe) => {
-                apply_image(fe, region, subregion, tree, ts)
+                apply_image(fe, region, subregion, tree, opt, ts)
             }
             usvg::FilterKind::FeComponentTransfer(ref fe) => {
                 let input = get_input(&fe.input, region, inputs, &results)?;
@@ -355,7 +367,7 @@ fn _apply(
                 apply_displacement_map(fe, region, filter.primitive_units, cs, bbox, ts, input1, input2)
             }
             usvg::FilterKind::FeTurbulence(ref fe) => {
-                apply_turbulence(fe, region, cs, ts)
+                apply_turbulence(fe, region, cs, opt, ts)
             }
             usvg::FilterKind::FeDiffuseLighting(ref fe) => {
                 let input = get_input(&fe.input, region, inputs, &results)?;
@@ -595,10 +607,14 @@ fn apply_blur(
     input: Image,
 ) -> Result<Image, Error> {
     let (std_dx, std_dy, box_blur)
-        = try_opt_or!(resolve_std_dev(fe, units, bbox, ts), Ok(input));
+        = try_opt_or!(resolve_std_dev(fe.std_dev_x, fe.std_dev_y, units, bbox, ts), Ok(input));
 
     let mut buffer = input.into_color_space(cs)?.take()?;
+    blur(std_dx, std_dy, box_blur, &mut buffer);
+    Ok(Image::from_image(buffer, cs))
+}
 
+fn blur(std_dx: f64, std_dy: f64, box_blur: bool, buffer: &mut skia::Surface) {
     // Skia surface can be RGBA, but it will not affect the blur algorithm.
     svgfilters::multiply_alpha(buffer.data_mut().as_rgba_mut());
 
@@ -609,8 +625,6 @@ fn apply_blur(
     }
 
     svgfilters::demultiply_alpha(buffer.data_mut().as_rgba_mut());
-
-    Ok(Image::from_image(buffer, cs))
 }
 
 fn apply_offset(
@@ -640,6 +654,56 @@ fn apply_offset(
     Ok(Image::from_image(buffer, input.color_space))
 }
 
+fn apply_drop_shadow(
+    fe: &usvg::FeDropShadow,
+    units: usvg::Units,
+    cs: ColorSpace,
+    bbox: Option<Rect>,
+    ts: &usvg::Transform,
+    input: Image,
+) -> Result<Image, Error> {
+    let (dx, dy) = try_opt_or!(scale_coordinates(fe.dx, fe.dy, units, bbox, ts), Ok(input));
+
+    // The shadow is the input alpha filled with the flood color,
+    // so the input colors do not affect it.
+    let mut shadow = input.as_ref().try_clone().ok_or(Error::AllocFailed)?;
+    let c = fe.color;
+    for p in shadow.data_mut().chunks_mut(4) {
+        p[0] = c.red;
+        p[1] = c.green;
+        p[2] = c.blue;
+        p[3] = (p[3] as f64 * fe.opacity.value()).round() as u8;
+    }
+
+    let std_dev = resolve_std_dev(fe.std_dev_x, fe.std_dev_y, units, bbox, ts);
+    if let Some((std_dx, std_dy, box_blur)) = std_dev {
+        blur(std_dx, std_dy, box_blur, &mut shadow);
+    }
+
+    let shadow = Image::from_image(shadow, ColorSpace::SRGB).into_color_space(cs)?;
+    let input = input.into_color_space(cs)?;
+
+    let mut buffer = skia::Surface::try_create(input.width(), input.height())?;
+    buffer.reset_transform();
+    buffer.draw_surface(
+        shadow.as_ref(),
+        dx as f32, dy as f32,
+        255,
+        skia::BlendMode::SourceOver,
+        skia::FilterQuality::Low,
+    );
+    buffer.draw_surface(
+        input.as_ref(),
+        0.0, 0.0,
+        255,
+        skia::BlendMode::SourceOver,
+        skia::FilterQuality::Low,
+    );
+    buffer.flush();
+
+    Ok(Image::from_image(buffer, cs))
+}
+
 fn apply_blend(
     fe: &usvg::FeBlend,
     cs: ColorSpace,
@@ -666,6 +730,17 @@ fn apply_blend(
         usvg::FeBlendMode::Screen => skia::BlendMode::Screen,
         usvg::FeBlendMode::Darken => skia::BlendMode::Darken,
         usvg::FeBlendMode::Lighten => skia::BlendMode::Lighten,
+        usvg::FeBlendMode::Overlay => skia::BlendMode::Overlay,
+        usvg::FeBlendMode::ColorDodge => skia::BlendMode::ColorDodge,
+        usvg::FeBlendMode::ColorBurn => skia::BlendMode::ColorBurn,
+        usvg::FeBlendMode::HardLight => skia::BlendMode::HardLight,
+        usvg::FeBlendMode::SoftLight => skia::BlendMode::SoftLight,
+        usvg::FeBlendMode::Difference => skia::BlendMode::Difference,
+        usvg::FeBlendMode::Exclusion => skia::BlendMode::Exclusion,
+        usvg::FeBlendMode::Hue => skia::BlendMode::Hue,
+        usvg::FeBlendMode::Saturation => skia::BlendMode::Saturation,
+        usvg::FeBlendMode::Color => skia::BlendMode::Color,
+        usvg::FeBlendMode::Luminosity => skia::BlendMode::Luminosity,
     };
 
     buffer.draw_surface(
@@ -802,6 +877,7 @@ fn apply_image(
     region: ScreenRect,
     subregion: ScreenRect,
     tree: &usvg::Tree,
+    opt: &RenderOptions,
     ts: &usvg::Transform,
 ) -> Result<Image, Error> {
     let mut buffer = skia::Surface::try_create(region.width(), region.height())?;
@@ -817,7 +893,7 @@ fn apply_image(
                 aspect: fe.aspect,
             };
 
-            crate::image::draw_kind(kind, view_box, fe.rendering_mode, &mut buffer);
+            crate::image::draw_kind(kind, opt, view_box, fe.rendering_mode, &mut buffer);
         }
         usvg::FeImageKind::Use(ref id) => {
             if let Some(ref node) = tree.defs_by_id(id).or(tree.node_by_id(id)) {
@@ -827,7 +903,7 @@ fn apply_image(
                 buffer.scale(sx as f32, sy as f32);
                 buffer.concat(node.transform().to_native());
 
-                crate::render::render_node(node, &mut RenderState::Ok, &mut layers, &mut buffer);
+                crate::render::render_node(node, opt, &mut RenderState::Ok, &mut layers, &mut buffer);
             }
         }
     }
@@ -980,6 +1056,7 @@ fn apply_turbulence(
     fe: &usvg::FeTurbulence,
     region: ScreenRect,
     cs: ColorSpace,
+    opt: &RenderOptions,
     ts: &usvg::Transform,
 ) -> Result<Image, Error> {
     let mut buffer = skia::Surface::try_create(region.width(), region.height())?;
@@ -994,7 +1071,7 @@ fn apply_turbulence(
         sx, sy,
         fe.base_frequency.x.value().into(), fe.base_frequency.y.value().into(),
         fe.num_octaves,
-        fe.seed,
+        fe.seed.wrapping_add(opt.turbulence_seed_offset),
         fe.stitch_tiles,
         fe.kind == usvg::FeTurbulenceKind::FractalNoise,
         into_svgfilters_image_mut!(buffer),
@@ -1076,19 +1153,20 @@ fn apply_to_canvas(
 ///
 /// If the last flag is set, then a box blur should be used. Or IIR otherwise.
 fn resolve_std_dev(
-    fe: &usvg::FeGaussianBlur,
+    std_dev_x: usvg::PositiveNumber,
+    std_dev_y: usvg::PositiveNumber,
     units: usvg::Units,
     bbox: Option<Rect>,
     ts: &usvg::Transform,
 ) -> Option<(f64, f64, bool)> {
     // 'A negative value or a value of zero disables the effect of the given filter primitive
     // (i.e., the result is the filter input image).'
-    if fe.std_dev_x.is_zero() && fe.std_dev_y.is_zero() {
+    if std_dev_x.is_zero() && std_dev_y.is_zero() {
         return None;
     }
 
     let (std_dx, std_dy) = scale_coordinates(
-        fe.std_dev_x.value(), fe.std_dev_y.value(), units, bbox, ts,
+        std_dev_x.value(), std_dev_y.value(), units, bbox, ts,
     )?;
     if std_dx.is_fuzzy_zero() && std_dy.is_fuzzy_zero() {
         None
@@ -0,0 +1,119 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::render::prelude::*;
+
+/// Returns the topmost path at the provided point.
+///
+/// The point is in the image coordinates, i.e. after `FitTo` is applied.
+/// Each path is tested according to its `pointer-events` value.
+/// By default, only visible paths with a fill or a stroke can be hit
+/// and only by their painted area.
+/// Clip paths, masks, opacity and filters are ignored.
+///
+/// Returns `None` when there is nothing at the point.
+pub fn hit_test(
+    tree: &usvg::Tree,
+    fit_to: usvg::FitTo,
+    x: f64,
+    y: f64,
+) -> Option<usvg::Node> {
+    let img_size = fit_to.fit_to(tree.svg_node().size.to_screen_size())?;
+    let view_box = tree.svg_node().view_box;
+    let root_ts = utils::view_box_to_transform(view_box.rect, view_box.aspect, img_size.to_size());
+
+    // The point is tested by rendering a path onto a single pixel,
+    // so it will be checked exactly like it is drawn.
+    let mut surface = crate::render::create_subsurface(ScreenSize::new(1, 1)?)?;
+
+    let mut hit = None;
+    for node in tree.root().descendants() {
+        if let usvg::NodeKind::Path(ref path) = *node.borrow() {
+            if path.pointer_events == usvg::PointerEvents::None || tree.is_in_defs(&node) {
+                continue;
+            }
+
+            if path.pointer_events.requires_visibility() && path.visibility != usvg::Visibility::Visible {
+                continue;
+            }
+
+            // Move the point to the pixel center.
+            let mut ts = usvg::Transform::new_translate(0.5 - x, 0.5 - y);
+            ts.append(&root_ts);
+            ts.append(&node.abs_transform());
+            ts.append(&path.transform);
+
+            // Paths are in the z-order, so the last hit is the topmost one.
+            if contains(path, ts, &mut surface) {
+                hit = Some(node.clone());
+            }
+        }
+    }
+
+    hit
+}
+
+fn contains(path: &usvg::Path, ts: usvg::Transform, surface: &mut skia::Surface) -> bool {
+    use usvg::PointerEvents;
+
+    surface.fill(0, 0, 0, 0);
+    surface.set_transform(ts.to_native());
+
+    if path.pointer_events == PointerEvents::BoundingBox {
+        if let Some(bbox) = path.data.bbox() {
+            let mut paint = skia::Paint::new();
+            paint.set_style(skia::PaintStyle::Fill);
+            paint.set_color(0, 0, 0, 255);
+            paint.set_anti_alias(false);
+            surface.draw_rect(
+                bbox.x() as f32, bbox.y() as f32, bbox.width() as f32, bbox.height() as f32, &paint,
+            );
+        }
+
+        return surface.data()[3] != 0;
+    }
+
+    let mut skia_path = crate::path::convert_path(&path.data);
+
+    let (test_fill, test_stroke) = match path.pointer_events {
+        PointerEvents::VisibleFill | PointerEvents::Fill => (true, false),
+        PointerEvents::VisibleStroke | PointerEvents::Stroke => (false, true),
+        PointerEvents::Visible | PointerEvents::All => (true, true),
+        _ => (path.fill.is_some(), path.stroke.is_some()),
+    };
+
+    if test_fill {
+        if path.fill.as_ref().map(|f| f.rule) == Some(usvg::FillRule::EvenOdd) {
+            skia_path.set_fill_type(skia::FillType::EvenOdd);
+        }
+
+        let mut paint = skia::Paint::new();
+        paint.set_style(skia::PaintStyle::Fill);
+        paint.set_color(0, 0, 0, 255);
+        paint.set_anti_alias(false);
+        surface.draw_path(&skia_path, &paint);
+    }
+
+    // An unpainted stroke still has a geometry defined by the stroke properties,
+    // but usvg doesn't preserve them, so the default ones are used.
+    if test_stroke {
+        let default_stroke;
+        let stroke = match path.stroke {
+            Some(ref stroke) => stroke,
+            None => {
+                default_stroke = usvg::Stroke::default();
+                &default_stroke
+            }
+        };
+
+        let mut paint = skia::Paint::new();
+        paint.set_style(skia::PaintStyle::Stroke);
+        paint.set_color(0, 0, 0, 255);
+        paint.set_anti_alias(false);
+        crate::paint_server::set_stroke_style(stroke, &mut paint);
+        surface.draw_path(&skia_path, &paint);
+    }
+
+    surface.data()[3] != 0
+}
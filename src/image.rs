@@ -2,46 +2,125 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
 use log::warn;
 use crate::render::prelude::*;
 
+/// A decoded raster images cache.
+///
+/// Embedded PNG and JPEG images are decoded only once per cache,
+/// which speeds up rendering the same SVG multiple times, e.g. at different sizes.
+/// Images are identified by their encoded data.
+///
+/// The cache is owned by the caller and is never cleared automatically.
+/// Cloning is cheap, since clones share the same storage.
+#[derive(Clone, Default)]
+pub struct ImageCache(Arc<Mutex<ImagesMap>>);
+
+/// Decoded images by the encoded data hash and length.
+type ImagesMap = HashMap<(u64, usize), Arc<Image>>;
+
+impl ImageCache {
+    /// Creates a new, empty cache.
+    pub fn new() -> Self {
+        ImageCache::default()
+    }
+
+    /// Returns the number of cached images.
+    pub fn len(&self) -> usize {
+        self.0.lock().map(|map| map.len()).unwrap_or(0)
+    }
+
+    /// Checks that the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all cached images.
+    pub fn clear(&self) {
+        if let Ok(mut map) = self.0.lock() {
+            map.clear();
+        }
+    }
+
+    fn get_or_decode(&self, data: &[u8], decode: fn(&[u8]) -> Option<Image>) -> Option<Arc<Image>> {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        data.hash(&mut hasher);
+        let key = (hasher.finish(), data.len());
+
+        if let Some(image) = self.0.lock().ok()?.get(&key) {
+            return Some(image.clone());
+        }
+
+        // Do not hold the lock while decoding.
+        let image = Arc::new(decode(data)?);
+        self.0.lock().ok()?.insert(key, image.clone());
+        Some(image)
+    }
+}
+
+impl std::fmt::Debug for ImageCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("ImageCache")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
 pub fn draw(
     image: &usvg::Image,
+    opt: &RenderOptions,
     canvas: &mut skia::Canvas,
 ) -> Rect {
     if image.visibility != usvg::Visibility::Visible {
         return image.view_box.rect;
     }
 
-    draw_kind(&image.kind, image.view_box, image.rendering_mode, canvas);
+    draw_kind(&image.kind, opt, image.view_box, image.rendering_mode, canvas);
     image.view_box.rect
 }
 
 pub fn draw_kind(
     kind: &usvg::ImageKind,
+    opt: &RenderOptions,
     view_box: usvg::ViewBox,
     rendering_mode: usvg::ImageRendering,
     canvas: &mut skia::Canvas,
 ) {
     match kind {
         usvg::ImageKind::JPEG(ref data) => {
-            match read_jpeg(data) {
+            match decode(data, opt, read_jpeg) {
                 Some(image) => draw_raster(&image, view_box, rendering_mode, canvas),
                 None => warn!("Failed to load an embedded image."),
             }
         }
         usvg::ImageKind::PNG(ref data) => {
-            match read_png(data) {
+            match decode(data, opt, read_png) {
                 Some(image) => draw_raster(&image, view_box, rendering_mode, canvas),
                 None => warn!("Failed to load an embedded image."),
             }
         }
         usvg::ImageKind::SVG(ref subtree) => {
-            draw_svg(subtree, view_box, canvas);
+            draw_svg(subtree, opt, view_box, canvas);
         }
     }
 }
 
+/// Decodes an image using `RenderOptions::image_cache` when set.
+fn decode(
+    data: &[u8],
+    opt: &RenderOptions,
+    decode: fn(&[u8]) -> Option<Image>,
+) -> Option<Arc<Image>> {
+    match opt.image_cache {
+        Some(ref cache) => cache.get_or_decode(data, decode),
+        None => decode(data).map(Arc::new),
+    }
+}
+
 fn draw_raster(
     img: &Image,
     view_box: usvg::ViewBox,
@@ -116,6 +195,7 @@ fn image_to_surface(image: &Image, surface: &mut [u8]) {
 
 fn draw_svg(
     tree: &usvg::Tree,
+    opt: &RenderOptions,
     view_box: usvg::ViewBox,
     canvas: &mut skia::Canvas,
 ) {
@@ -131,7 +211,14 @@ fn draw_svg(
     }
 
     canvas.concat(ts.to_native());
-    render_to_canvas(&tree, img_size, canvas);
+    if opt.progress.is_some() {
+        // Only the main tree rendering is reported.
+        let mut opt = opt.clone();
+        opt.progress = None;
+        render_to_canvas(tree, &opt, img_size, canvas);
+    } else {
+        render_to_canvas(tree, opt, img_size, canvas);
+    }
 
     canvas.restore();
 }
@@ -11,25 +11,57 @@
 #![warn(missing_docs)]
 
 pub use rgb::RGBA8;
+pub use tiny_skia;
 pub use usvg::ScreenSize;
 
-use usvg::NodeExt;
+pub use crate::atlas::*;
+pub use crate::diff::*;
+pub use crate::error::*;
+pub use crate::hit_test::*;
+pub use crate::image::ImageCache;
+pub use crate::options::*;
+pub use crate::premultiplied::*;
+pub use crate::quantize::*;
+#[cfg(feature = "sdf")]
+pub use crate::sdf::*;
+pub use crate::task::*;
+
+use usvg::{FuzzyZero, NodeExt};
 use log::warn;
 
+mod atlas;
 mod clip;
+mod diff;
+mod error;
 mod filter;
+mod hit_test;
 mod image;
 mod layers;
 mod macros;
 mod mask;
+mod options;
+mod outline;
 mod paint_server;
 mod path;
+mod premultiplied;
+mod quantize;
 mod render;
+#[cfg(feature = "sdf")]
+mod sdf;
+mod task;
 
 
 /// A raster image that contains rendering results.
 ///
 /// Unpremultiplied RGBA color type is used.
+///
+/// Since the data is already demultiplied and tightly packed,
+/// it can be passed to the `image` crate as is:
+///
+/// ```ignore
+/// let img = resvg::render(&tree, usvg::FitTo::Original, None).unwrap();
+/// let img = image::RgbaImage::from_raw(img.width(), img.height(), img.take()).unwrap();
+/// ```
 #[derive(Clone)]
 pub struct Image {
     data: Vec<u8>,
@@ -82,6 +114,15 @@ impl Image {
 
     /// Save the image as PNG at a provided path.
     pub fn save_png<P: AsRef<std::path::Path>>(&self, path: P) -> Result<(), png::EncodingError> {
+        self.save_png_with_options(path, &PngOptions::default())
+    }
+
+    /// Save the image as PNG at a provided path using the provided encoding options.
+    pub fn save_png_with_options<P: AsRef<std::path::Path>>(
+        &self,
+        path: P,
+        opt: &PngOptions,
+    ) -> Result<(), png::EncodingError> {
         let file = std::fs::File::create(path)?;
         let ref mut w = std::io::BufWriter::new(file);
 
@@ -90,6 +131,22 @@ impl Image {
         encoder.set_depth(png::BitDepth::Eight);
 
         let mut writer = encoder.write_header()?;
+
+        if opt.srgb {
+            // Perceptual rendering intent.
+            writer.write_chunk(*b"sRGB", &[0])?;
+
+            // Fallback `gAMA` and `cHRM` values for decoders without `sRGB` support,
+            // as recommended by the PNG spec.
+            writer.write_chunk(*b"gAMA", &45455u32.to_be_bytes())?;
+
+            let mut chrm = Vec::with_capacity(32);
+            for n in &[31270u32, 32900, 64000, 33000, 30000, 60000, 15000, 6000] {
+                chrm.extend_from_slice(&n.to_be_bytes());
+            }
+            writer.write_chunk(*b"cHRM", &chrm)?;
+        }
+
         writer.write_image_data(&self.data())
     }
 }
@@ -101,9 +158,18 @@ pub fn render(
     fit_to: usvg::FitTo,
     background: Option<usvg::Color>,
 ) -> Option<Image> {
-    let (mut img, img_size)
-        = render::create_root_image(tree.svg_node().size.to_screen_size(), fit_to, background)?;
-    render::render_to_canvas(tree, img_size, &mut img);
+    render_with_options(tree, fit_to, background, &RenderOptions::default())
+}
+
+/// Renders an SVG to image using the provided rendering options.
+pub fn render_with_options(
+    tree: &usvg::Tree,
+    fit_to: usvg::FitTo,
+    background: Option<usvg::Color>,
+    opt: &RenderOptions,
+) -> Option<Image> {
+    let (view_box, size) = root_view_box(tree, opt);
+    let img = render_root(&tree.root(), view_box, size, fit_to, background, opt)?;
     Some(Image::from_surface(img))
 }
 
@@ -112,6 +178,16 @@ pub fn render_node(
     node: &usvg::Node,
     fit_to: usvg::FitTo,
     background: Option<usvg::Color>,
+) -> Option<Image> {
+    render_node_with_options(node, fit_to, background, &RenderOptions::default())
+}
+
+/// Renders an SVG node to image using the provided rendering options.
+pub fn render_node_with_options(
+    node: &usvg::Node,
+    fit_to: usvg::FitTo,
+    background: Option<usvg::Color>,
+    opt: &RenderOptions,
 ) -> Option<Image> {
     let node_bbox = if let Some(bbox) = node.calculate_bbox() {
         bbox
@@ -125,9 +201,528 @@ pub fn render_node(
         aspect: usvg::AspectRatio::default(),
     };
 
-    let (mut img, img_size)
-        = render::create_root_image(node_bbox.size().to_screen_size(), fit_to, background)?;
-
-    render::render_node_to_canvas(node, vbox, img_size, &mut render::RenderState::Ok, &mut img);
+    let img = render_root(node, vbox, node_bbox.size().to_screen_size(), fit_to, background, opt)?;
     Some(Image::from_surface(img))
 }
+
+/// Returns the size of an image that `render` would produce.
+///
+/// Doesn't allocate or render anything.
+/// The result doesn't depend on the `preserveAspectRatio` of the root element,
+/// since `slice` content is clipped by the image bounds and not rendered beyond them.
+pub fn compute_render_size(
+    tree: &usvg::Tree,
+    fit_to: usvg::FitTo,
+) -> Option<ScreenSize> {
+    fit_to.fit_to(tree.svg_node().size.to_screen_size())
+}
+
+/// Returns the size of an image that `render_with_options` would produce.
+///
+/// Unlike `compute_render_size`, takes `RenderOptions::padding`
+/// and `RenderOptions::ignore_root_overflow` into account.
+/// Doesn't allocate or render anything.
+pub fn compute_render_size_with_options(
+    tree: &usvg::Tree,
+    fit_to: usvg::FitTo,
+    opt: &RenderOptions,
+) -> Option<ScreenSize> {
+    let (_, size) = root_view_box(tree, opt);
+    output_image_size(size, fit_to, opt)
+}
+
+/// Returns the number of pixels of the biggest image allocated by `render_with_options`.
+///
+/// Supersampled and pixelated images are rendered at a different size first,
+/// which can be bigger than the resulting one.
+fn compute_render_pixels(
+    tree: &usvg::Tree,
+    fit_to: usvg::FitTo,
+    opt: &RenderOptions,
+) -> Option<u64> {
+    let pixels = |size: ScreenSize| size.width() as u64 * size.height() as u64;
+    let (_, size) = root_view_box(tree, opt);
+    let canvas_size = root_image_size(size, fit_to, opt)?;
+    let img_size = output_image_size(size, fit_to, opt)?;
+    Some(std::cmp::max(pixels(canvas_size), pixels(img_size)))
+}
+
+/// Renders an SVG to image, reporting all failures as errors.
+///
+/// Unlike `render_with_options`, this function never panics:
+/// a panic inside the renderer is caught and reported as `RenderError::Panicked`.
+/// Images with more than `max_pixels` pixels are rejected before any allocation.
+/// Intermediate images, like a supersampled one, are counted too.
+///
+/// Intended for rendering untrusted SVG files.
+pub fn try_render(
+    tree: &usvg::Tree,
+    fit_to: usvg::FitTo,
+    background: Option<usvg::Color>,
+    opt: &RenderOptions,
+    max_pixels: u64,
+) -> Result<Image, RenderError> {
+    let pixels = compute_render_pixels(tree, fit_to, opt).ok_or(RenderError::InvalidSize)?;
+    if pixels > max_pixels {
+        return Err(RenderError::ImageTooBig);
+    }
+
+    // `usvg::Tree` is not `UnwindSafe` because of the interior mutability,
+    // but it's not modified during rendering, so a broken state cannot be observed.
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        render_with_options(tree, fit_to, background, opt)
+    }));
+
+    match res {
+        Ok(Some(img)) => Ok(img),
+        Ok(None) => Err(RenderError::AllocFailed),
+        Err(_) => Err(RenderError::Panicked),
+    }
+}
+
+/// Renders an SVG to image, reducing the image size to fit the pixels budget.
+///
+/// Same as `try_render`, but instead of rejecting images with more than `max_pixels` pixels,
+/// renders them at a smaller scale, preserving the aspect ratio.
+///
+/// Returns an image and a scale that was applied to the requested size.
+/// The scale is `1.0` when the image fits the budget.
+pub fn try_render_within_budget(
+    tree: &usvg::Tree,
+    fit_to: usvg::FitTo,
+    background: Option<usvg::Color>,
+    opt: &RenderOptions,
+    max_pixels: u64,
+) -> Result<(Image, f64), RenderError> {
+    let (_, root_size) = root_view_box(tree, opt);
+    let size = fit_to.fit_to(root_size).ok_or(RenderError::InvalidSize)?;
+    let pixels = compute_render_pixels(tree, fit_to, opt).ok_or(RenderError::InvalidSize)?;
+    if pixels <= max_pixels {
+        return try_render(tree, fit_to, background, opt, max_pixels).map(|img| (img, 1.0));
+    }
+
+    let scale = (max_pixels as f64 / pixels as f64).sqrt();
+    let mut width = (size.width() as f64 * scale) as u32;
+
+    // `FitTo::Width` rounds the height up and padding doesn't scale,
+    // so the image can still be slightly bigger.
+    loop {
+        if width == 0 {
+            return Err(RenderError::ImageTooBig);
+        }
+
+        let new_pixels = compute_render_pixels(tree, usvg::FitTo::Width(width), opt)
+            .ok_or(RenderError::InvalidSize)?;
+        if new_pixels <= max_pixels {
+            break;
+        }
+
+        width -= 1;
+    }
+
+    let img = try_render(tree, usvg::FitTo::Width(width), background, opt, max_pixels)?;
+    Ok((img, width as f64 / size.width() as f64))
+}
+
+/// Renders each top-level group of an SVG to a separate image.
+///
+/// Each group is rendered in isolation, but with the same canvas size and transform
+/// as the whole SVG, so the resulting images can be composited back together.
+/// Groups without an ID are skipped.
+/// When `ids` is set, only groups with the listed IDs are rendered.
+///
+/// Named groups are preserved only when the tree was parsed
+/// with `usvg::Options::keep_named_groups` enabled.
+///
+/// Returns images in the document order.
+pub fn render_layers(
+    tree: &usvg::Tree,
+    ids: Option<&[&str]>,
+    fit_to: usvg::FitTo,
+    background: Option<usvg::Color>,
+    opt: &RenderOptions,
+) -> Vec<(String, Image)> {
+    let (view_box, size) = root_view_box(tree, opt);
+
+    let mut layers = Vec::new();
+    for node in tree.root().children() {
+        if !matches!(*node.borrow(), usvg::NodeKind::Group(_)) || node.id().is_empty() {
+            continue;
+        }
+
+        if let Some(ids) = ids {
+            if !ids.contains(&&*node.id()) {
+                continue;
+            }
+        }
+
+        if let Some(img) = render_root(&node, view_box, size, fit_to, background, opt) {
+            layers.push((node.id().to_string(), Image::from_surface(img)));
+        }
+    }
+
+    layers
+}
+
+/// Renders multiple nodes, each cropped to its bounding box.
+///
+/// Same as calling `render_node_with_options` for each `Tree::node_by_id` result,
+/// but the tree is traversed only once and, unless `RenderOptions::image_cache` is set,
+/// embedded raster images are decoded only once for all nodes.
+///
+/// Returns images in the `ids` order.
+/// IDs that were not found or nodes that cannot be rendered are skipped.
+pub fn render_nodes(
+    tree: &usvg::Tree,
+    ids: &[&str],
+    fit_to: usvg::FitTo,
+    background: Option<usvg::Color>,
+    opt: &RenderOptions,
+) -> Vec<(String, Image)> {
+    let mut nodes: Vec<Option<usvg::Node>> = vec![None; ids.len()];
+    for node in tree.root().descendants() {
+        if tree.is_in_defs(&node) {
+            continue;
+        }
+
+        let id = node.id();
+        if id.is_empty() {
+            continue;
+        }
+
+        // Like `node_by_id`, the first node with a matching ID wins.
+        for (i, _) in ids.iter().enumerate().filter(|(_, v)| **v == &*id) {
+            if nodes[i].is_none() {
+                nodes[i] = Some(node.clone());
+            }
+        }
+    }
+
+    let mut opt = opt.clone();
+    if opt.image_cache.is_none() {
+        opt.image_cache = Some(ImageCache::new());
+    }
+
+    let mut images = Vec::new();
+    for (id, node) in ids.iter().zip(nodes) {
+        if let Some(img) = node.and_then(|n| render_node_with_options(&n, fit_to, background, &opt)) {
+            images.push((id.to_string(), img));
+        }
+    }
+
+    images
+}
+
+/// Returns the tree view box and size with `RenderOptions::view_box`
+/// and `RenderOptions::ignore_root_overflow` applied.
+fn root_view_box(tree: &usvg::Tree, opt: &RenderOptions) -> (usvg::ViewBox, ScreenSize) {
+    let svg = tree.svg_node();
+    let mut view_box = match opt.view_box {
+        Some(rect) => usvg::ViewBox { rect, aspect: svg.view_box.aspect },
+        None => svg.view_box,
+    };
+    let mut size = svg.size.to_screen_size();
+
+    if opt.ignore_root_overflow {
+        // `calculate_bbox` returns `Rect::new_bbox()` when there is nothing to render.
+        let bbox = tree.root().calculate_bbox().filter(|r| r.x() != f64::MAX);
+        if let Some(bbox) = bbox {
+            let rect = view_box.rect.expand(bbox);
+
+            // Keep the content scale, like `usvg::Tree::to_cropped_string` does.
+            let ts = usvg::utils::view_box_to_transform(view_box.rect, view_box.aspect, svg.size);
+            let (sx, sy) = ts.get_scale();
+            if let Some(new_size) = usvg::Size::new(rect.width() * sx, rect.height() * sy) {
+                view_box.rect = rect;
+                size = new_size.to_screen_size();
+            }
+        }
+    }
+
+    (view_box, size)
+}
+
+/// Renders a node onto a new root image using the provided view box.
+fn render_root(
+    node: &usvg::Node,
+    view_box: usvg::ViewBox,
+    size: ScreenSize,
+    fit_to: usvg::FitTo,
+    background: Option<usvg::Color>,
+    opt: &RenderOptions,
+) -> Option<tiny_skia::Surface> {
+    let (mut img, img_size) = create_root_image(size, fit_to, background, opt)?;
+    render::render_node_to_canvas(node, opt, view_box, img_size, &mut render::RenderState::Ok, &mut img);
+    finish_root_image(node, view_box, size, fit_to, img, background, opt)
+}
+
+fn create_root_image(
+    size: ScreenSize,
+    fit_to: usvg::FitTo,
+    background: Option<usvg::Color>,
+    opt: &RenderOptions,
+) -> Option<(tiny_skia::Surface, ScreenSize)> {
+    let img_size = root_image_size(size, fit_to, opt)?;
+    let (mut img, img_size) = render::create_root_image(img_size, usvg::FitTo::Original, background)?;
+
+    // Flip only the content, so post-processing is done in the image coordinates.
+    if opt.flip_y {
+        render::apply_flip_transform(img_size, &mut img);
+    }
+
+    Some((img, img_size))
+}
+
+/// Returns the size of an image that the content is rendered onto.
+fn root_image_size(
+    size: ScreenSize,
+    fit_to: usvg::FitTo,
+    opt: &RenderOptions,
+) -> Option<ScreenSize> {
+    // In the pixelated mode, an image is rendered at its original size first
+    // and then scaled to the requested one.
+    let fit_to = if opt.pixelated { usvg::FitTo::Original } else { fit_to };
+    let img_size = fit_to.fit_to(size)?;
+
+    // In the supersampling mode, an image is rendered at a bigger size first
+    // and then scaled down to the requested one.
+    if let Some(factor) = supersample_factor(opt) {
+        return ScreenSize::new(
+            img_size.width().checked_mul(factor)?,
+            img_size.height().checked_mul(factor)?,
+        );
+    }
+
+    Some(img_size)
+}
+
+/// Returns the size of the resulting image, after all post-processing.
+fn output_image_size(
+    size: ScreenSize,
+    fit_to: usvg::FitTo,
+    opt: &RenderOptions,
+) -> Option<ScreenSize> {
+    let img_size = fit_to.fit_to(size)?;
+    let padding = opt.padding.checked_mul(2)?;
+    ScreenSize::new(
+        img_size.width().checked_add(padding)?,
+        img_size.height().checked_add(padding)?,
+    )
+}
+
+/// Returns the `RenderOptions::supersample` factor, unless it has no effect.
+fn supersample_factor(opt: &RenderOptions) -> Option<u32> {
+    match opt.supersample {
+        Some(factor) if factor > 1 && !opt.pixelated => Some(factor),
+        _ => None,
+    }
+}
+
+/// Applies post-processing requested by the `opt` to the rendered root image.
+fn finish_root_image(
+    node: &usvg::Node,
+    view_box: usvg::ViewBox,
+    size: ScreenSize,
+    fit_to: usvg::FitTo,
+    mut img: tiny_skia::Surface,
+    background: Option<usvg::Color>,
+    opt: &RenderOptions,
+) -> Option<tiny_skia::Surface> {
+    let mut img_size = fit_to.fit_to(size)?;
+
+    if opt.pixelated {
+        img = render::resize_nearest(&img, img_size)?;
+    }
+
+    if let Some(factor) = supersample_factor(opt) {
+        img = render::downsample_box(&img, factor, img_size)?;
+    }
+
+    if let Some(ref clip) = opt.clip {
+        clip::clip_surface(clip, &mut img);
+    }
+
+    // Must be drawn before padding, since it relies on the image transform.
+    if opt.draw_bboxes {
+        img.reset_transform();
+        if opt.flip_y {
+            render::apply_flip_transform(img_size, &mut img);
+        }
+        render::draw_bboxes(node, view_box, img_size, &mut img);
+    }
+
+    if opt.padding != 0 {
+        img = render::add_padding(&img, opt.padding, background)?;
+        img_size = ScreenSize::new(img.width(), img.height())?;
+    }
+
+    if let Some(ref checkerboard) = opt.checkerboard {
+        let mut bg_img = render::create_subsurface(img_size)?;
+        render::draw_checkerboard(checkerboard, img_size, &mut bg_img);
+        bg_img.draw_surface(
+            &img, 0.0, 0.0, 255, tiny_skia::BlendMode::SourceOver, tiny_skia::FilterQuality::Low,
+        );
+        img = bg_img;
+    }
+
+    if let Some(gamma) = opt.output_gamma {
+        render::apply_gamma(gamma, &mut img);
+    }
+
+    Some(img)
+}
+
+/// A path collected by `tree_to_paths`.
+#[derive(Clone, Debug)]
+pub struct FilledPath {
+    /// Path element ID.
+    pub id: String,
+
+    /// A fill rule that should be used with `data`.
+    pub fill_rule: usvg::FillRule,
+
+    /// Path data in the root user space, i.e. in the `viewBox` coordinates.
+    pub data: usvg::PathData,
+}
+
+/// Collects the painted geometry of an SVG as separate paths.
+///
+/// All visible filled paths are transformed into the root user space
+/// and returned one by one, along with their fill rules.
+/// The paths are not unioned and can overlap, since `tiny-skia` has no path operations.
+/// Filling all of them, each using its own rule, covers the painted area of the SVG.
+/// Colors, opacity, clipping, masking and filters are ignored.
+///
+/// When `strokes` is set, strokes are outlined and returned as separate paths,
+/// which use the `nonzero` rule. Curves are flattened while outlining,
+/// with a maximum deviation of 0.1 in the root user space.
+///
+/// Useful for generating clip paths and masks from SVG images.
+pub fn tree_to_paths(tree: &usvg::Tree, strokes: bool) -> Vec<FilledPath> {
+    const STROKE_TOLERANCE: f64 = 0.1;
+
+    let mut paths = Vec::new();
+    for node in tree.root().descendants() {
+        if let usvg::NodeKind::Path(ref path) = *node.borrow() {
+            if path.visibility != usvg::Visibility::Visible || tree.is_in_defs(&node) {
+                continue;
+            }
+
+            let mut ts = node.abs_transform();
+            ts.append(&path.transform);
+
+            if let Some(ref fill) = path.fill {
+                let mut data = (*path.data).clone();
+                data.transform(ts);
+                paths.push(FilledPath {
+                    id: node.id().to_string(),
+                    fill_rule: fill.rule,
+                    data,
+                });
+            }
+
+            if let (true, Some(ref stroke)) = (strokes, &path.stroke) {
+                // The outline is built in the path coordinates, so the tolerance has to be scaled.
+                let (sx, sy) = ts.get_scale();
+                let scale = sx.max(sy);
+                if scale.is_fuzzy_zero() {
+                    continue;
+                }
+
+                let mut data = outline::stroke_to_path(&path.data, stroke, STROKE_TOLERANCE / scale);
+                if !data.is_empty() {
+                    data.transform(ts);
+                    paths.push(FilledPath {
+                        id: node.id().to_string(),
+                        fill_rule: usvg::FillRule::NonZero,
+                        data,
+                    });
+                }
+            }
+        }
+    }
+
+    paths
+}
+
+/// A flattened filled path.
+#[derive(Clone, Debug)]
+pub struct FlatPath {
+    /// Path element ID.
+    pub id: String,
+
+    /// A fill rule that should be used with `polygons`.
+    pub fill_rule: usvg::FillRule,
+
+    /// Closed polygons in the root user space, i.e. in the `viewBox` coordinates.
+    ///
+    /// The closing point is not duplicated.
+    pub polygons: Vec<Vec<(f64, f64)>>,
+}
+
+/// Collects the filled geometry of an SVG as polygons.
+///
+/// Like `tree_to_paths`, but curves are converted into line segments
+/// with the maximum deviation of `tolerance` in the root user space.
+/// Strokes are not outlined and are ignored.
+/// Subpaths with less than three points are skipped.
+///
+/// Returns `None` when `tolerance` is not a positive number.
+///
+/// Useful for generating meshes from SVG images.
+pub fn tree_to_polygons(tree: &usvg::Tree, tolerance: f64) -> Option<Vec<FlatPath>> {
+    if tolerance <= 0.0 || !tolerance.is_finite() {
+        return None;
+    }
+
+    let mut paths = Vec::new();
+    for node in tree.root().descendants() {
+        if let usvg::NodeKind::Path(ref path) = *node.borrow() {
+            let fill = match path.fill {
+                Some(ref fill) if path.visibility == usvg::Visibility::Visible => fill,
+                _ => continue,
+            };
+
+            if tree.is_in_defs(&node) {
+                continue;
+            }
+
+            let mut ts = node.abs_transform();
+            ts.append(&path.transform);
+
+            let mut data = (*path.data).clone();
+            data.transform(ts);
+            let data = data.flatten(tolerance);
+
+            let mut polygons = Vec::new();
+            for subpath in data.subpaths() {
+                let mut points = Vec::with_capacity(subpath.len());
+                for seg in subpath.iter() {
+                    match *seg {
+                          usvg::PathSegment::MoveTo { x, y }
+                        | usvg::PathSegment::LineTo { x, y } => points.push((x, y)),
+                        _ => {}
+                    }
+                }
+
+                if points.len() > 1 && points.first() == points.last() {
+                    points.pop();
+                }
+
+                if points.len() > 2 {
+                    polygons.push(points);
+                }
+            }
+
+            if !polygons.is_empty() {
+                paths.push(FlatPath {
+                    id: node.id().to_string(),
+                    fill_rule: fill.rule,
+                    polygons,
+                });
+            }
+        }
+    }
+
+    Some(paths)
+}
@@ -418,7 +418,10 @@ fn parse_args() -> Result<Args, String> {
         text_rendering: args.text_rendering,
         image_rendering: args.image_rendering,
         keep_named_groups,
+        reject_unknown_elements: false,
+        flatten_transforms: false,
         fontdb,
+        missing_text: usvg::MissingText::Skip,
     };
 
     Ok(Args {
@@ -7,6 +7,7 @@ use crate::render::prelude::*;
 pub fn mask(
     node: &usvg::Node,
     mask: &usvg::Mask,
+    opt: &RenderOptions,
     bbox: Rect,
     layers: &mut Layers,
     canvas: &mut skia::Canvas,
@@ -30,7 +31,9 @@ pub fn mask(
             mask_surface.concat(usvg::Transform::from_bbox(bbox).to_native());
         }
 
-        crate::render::render_group(node, &mut RenderState::Ok, layers, &mut mask_surface);
+        // A mask defines visibility and not ink, so it must be rendered as is.
+        let opt = opt.for_content();
+        crate::render::render_group(node, &opt, &mut RenderState::Ok, layers, &mut mask_surface);
 
         mask_surface.restore();
     }
@@ -45,7 +48,7 @@ pub fn mask(
     if let Some(ref id) = mask.mask {
         if let Some(ref mask_node) = node.tree().defs_by_id(id) {
             if let usvg::NodeKind::Mask(ref mask) = *mask_node.borrow() {
-                self::mask(mask_node, mask, bbox, layers, canvas);
+                self::mask(mask_node, mask, opt, bbox, layers, canvas);
             }
         }
     }
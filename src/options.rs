@@ -0,0 +1,469 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::ImageCache;
+
+/// A checkerboard pattern.
+#[derive(Clone, Copy, Debug)]
+pub struct Checkerboard {
+    /// A cell size in pixels.
+    pub size: u32,
+
+    /// A color of the top-left cell.
+    pub color1: usvg::Color,
+
+    /// A color of the adjacent cells.
+    pub color2: usvg::Color,
+}
+
+impl Default for Checkerboard {
+    fn default() -> Self {
+        Checkerboard {
+            size: 8,
+            color1: usvg::Color::new(255, 255, 255),
+            color2: usvg::Color::new(204, 204, 204),
+        }
+    }
+}
+
+
+/// PNG encoding options.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct PngOptions {
+    /// Marks an image as being in the sRGB color space.
+    ///
+    /// Writes the `sRGB` chunk along with the fallback `gAMA` and `cHRM` chunks,
+    /// so color-managed viewers will not reinterpret the image colors.
+    ///
+    /// Default: `false`
+    pub srgb: bool,
+}
+
+
+/// A path paint type.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PaintKind {
+    /// A fill paint.
+    Fill,
+    /// A stroke paint.
+    Stroke,
+}
+
+
+/// A callback that can modify a path paint right before drawing.
+///
+/// Receives a path node, a paint type and a paint that will be used for drawing.
+pub type PaintHook = std::sync::Arc<dyn Fn(&usvg::Node, PaintKind, &mut tiny_skia::Paint) + Send + Sync>;
+
+
+/// A callback that receives a rendering progress.
+///
+/// The progress is in a 0..1 range.
+pub type ProgressHook = std::sync::Arc<dyn Fn(f32) + Send + Sync>;
+
+
+/// Rendering options.
+#[derive(Clone, Default)]
+pub struct RenderOptions {
+    /// An opacity of the dash gaps.
+    ///
+    /// When set, dashed strokes will be rendered in two passes:
+    /// the whole stroke with an opacity multiplied by this value
+    /// and the dash pattern on top of it.
+    /// This allows rendering semi-transparent gaps, which are not possible in SVG.
+    ///
+    /// Has no effect on strokes without `stroke-dasharray`.
+    ///
+    /// Default: `None`
+    pub dash_gap_opacity: Option<usvg::Opacity>,
+
+    /// Draws bounding boxes of all nodes on top of the rendered image.
+    ///
+    /// Paths are outlined in red, images in blue and groups in green.
+    /// Useful for debugging the paint servers, clip paths and masks positioning,
+    /// since all of them depend on the bounding box.
+    ///
+    /// Default: `false`
+    pub draw_bboxes: bool,
+
+    /// Renders an image at its original size and then scales it
+    /// to the requested one using the nearest-neighbor filtering.
+    ///
+    /// Useful for pixel-art images, which should keep hard pixel edges
+    /// regardless of the output size.
+    /// Unlike `image-rendering`, affects the whole image and not only raster images.
+    ///
+    /// Default: `false`
+    pub pixelated: bool,
+
+    /// Renders an image on top of a checkerboard pattern.
+    ///
+    /// Useful for previewing images with transparency.
+    /// Has no effect when an opaque background color is set.
+    ///
+    /// Default: `None`
+    pub checkerboard: Option<Checkerboard>,
+
+    /// The maximum number of gradient stops.
+    ///
+    /// Gradients with more stops will be simplified by removing stops
+    /// that have the least visual impact.
+    /// Useful for machine-generated gradients with hundreds of stops.
+    ///
+    /// `None` keeps all stops.
+    ///
+    /// Default: `None`
+    pub max_gradient_stops: Option<usize>,
+
+    /// A callback that will be called for each fill and stroke paint.
+    ///
+    /// Allows changing the paint color, shader, blend mode, etc.
+    /// Isn't called for paths inside clip paths.
+    ///
+    /// Default: `None`
+    pub paint_hook: Option<PaintHook>,
+
+    /// The minimum size of a path in pixels.
+    ///
+    /// Paths with the width and height of the bounding box, including the stroke,
+    /// smaller than this value will be skipped.
+    /// Useful for rendering thumbnails of complex SVGs faster.
+    ///
+    /// `None` renders all paths.
+    ///
+    /// Default: `None`
+    pub min_path_size: Option<f64>,
+
+    /// Flips the image vertically, so the first row is the bottom one.
+    ///
+    /// Useful for consumers with a bottom-left origin, like OpenGL textures.
+    /// The content is rendered using a flipped transform, so unlike flipping
+    /// the resulting image, doesn't require an additional copy.
+    /// `RenderOptions::clip` and `RenderOptions::checkerboard` are still
+    /// in the image coordinates and are not flipped.
+    ///
+    /// Default: `false`
+    pub flip_y: bool,
+
+    /// A padding around the image in pixels.
+    ///
+    /// Applied after `FitTo`, so the resulting image will be `2 * padding` pixels
+    /// bigger than the requested size. The padding is filled with the background color
+    /// or left transparent when it isn't set.
+    /// Ignored by `compute_render_size`, use `compute_render_size_with_options` instead.
+    ///
+    /// Default: `0`
+    pub padding: u32,
+
+    /// Renders only strokes, skipping all fills.
+    ///
+    /// Useful for generating cut lines and registration marks.
+    /// Clip paths and masks are not affected, since they define visibility and not ink.
+    /// Raster images are still rendered.
+    ///
+    /// Default: `false`
+    pub strokes_only: bool,
+
+    /// A path that the whole image will be clipped by.
+    ///
+    /// The path is in the image coordinates, i.e. after `FitTo` is applied,
+    /// and is filled using the `nonzero` rule.
+    /// The background is clipped too.
+    ///
+    /// Default: `None`
+    pub clip: Option<usvg::PathData>,
+
+    /// Overrides the SVG `viewBox`.
+    ///
+    /// Useful for files with an invalid or missing `viewBox`.
+    /// The SVG size and `preserveAspectRatio` are still used,
+    /// so the image size will not change.
+    /// Has no effect on `render_node`.
+    ///
+    /// Default: `None`
+    pub view_box: Option<usvg::Rect>,
+
+    /// A decoded raster images cache.
+    ///
+    /// When set, embedded images will be decoded only once
+    /// across all renders that use the same cache.
+    ///
+    /// Default: `None`
+    pub image_cache: Option<ImageCache>,
+
+    /// Renders gradients as the specified number of solid color bands.
+    ///
+    /// Each band is filled with the gradient color at the band center.
+    /// Useful for draft previews and posterized output.
+    ///
+    /// `None` renders smooth gradients.
+    ///
+    /// Default: `None`
+    pub gradient_bands: Option<u32>,
+
+    /// A callback that will be called after each top-level node is rendered.
+    ///
+    /// The progress is based on the number of rendered nodes,
+    /// so top-level groups with more children will advance it further.
+    /// Has no effect on `render_node`.
+    ///
+    /// Default: `None`
+    pub progress: Option<ProgressHook>,
+
+    /// Renders an image at the specified number of times bigger size without
+    /// anti-aliasing and then scales it down to the requested one by averaging pixels.
+    ///
+    /// Usually faster than the default anti-aliasing for images with many small paths,
+    /// like icons, at the cost of a lower edges quality.
+    /// Values below 2 and the `pixelated` mode disable it.
+    ///
+    /// `None` uses the default anti-aliasing.
+    ///
+    /// Default: `None`
+    pub supersample: Option<u32>,
+
+    /// A gamma curve that will be applied to the rendered image.
+    ///
+    /// Each color channel is raised to the power of this value,
+    /// so values above 1 darken the image and values below 1 lighten it.
+    /// Alpha is not affected.
+    /// Useful for compositing the output in a linear color space,
+    /// where `2.2` approximately converts sRGB colors to linear ones.
+    ///
+    /// Applied last, after the background, padding and checkerboard.
+    /// Non-positive values are ignored.
+    ///
+    /// Default: `None`
+    pub output_gamma: Option<f32>,
+
+    /// A number that will be added to the `seed` of each `feTurbulence`.
+    ///
+    /// Allows producing different, but still deterministic, noise variants
+    /// from the same SVG. Primitives with different seeds remain different.
+    ///
+    /// Default: `0`
+    pub turbulence_seed_offset: i32,
+
+    /// Renders the content outside the root `viewBox` instead of clipping it.
+    ///
+    /// The view box is extended to include the content bounding box
+    /// and the image size grows accordingly, keeping the content scale.
+    /// Ignored by `compute_render_size`, use `compute_render_size_with_options` instead.
+    /// Has no effect on `render_node`.
+    ///
+    /// Default: `false`
+    pub ignore_root_overflow: bool,
+
+    /// Draws outlines of all paths instead of their fills and strokes.
+    ///
+    /// Each path, including the hidden and unpainted ones, is drawn
+    /// as a one pixel wide hairline of the specified color.
+    /// Useful for debugging overlapping or invisible geometry.
+    /// Clip paths, masks, opacity and filters are still applied.
+    /// Raster images are still rendered.
+    ///
+    /// Default: `None`
+    pub wireframe: Option<usvg::Color>,
+}
+
+impl RenderOptions {
+    /// Creates a new builder with default options.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let opt = resvg::RenderOptions::builder()
+    ///     .draw_bboxes(true)
+    ///     .max_gradient_stops(16)
+    ///     .build();
+    /// ```
+    pub fn builder() -> RenderOptionsBuilder {
+        RenderOptionsBuilder::default()
+    }
+
+    /// Returns options for rendering a content that is not a geometry,
+    /// like mask or pattern, which must be rendered as is.
+    ///
+    /// Resets `strokes_only` and `wireframe`. Doesn't clone when they are not set.
+    pub(crate) fn for_content(&self) -> std::borrow::Cow<'_, RenderOptions> {
+        if !self.strokes_only && self.wireframe.is_none() {
+            return std::borrow::Cow::Borrowed(self);
+        }
+
+        std::borrow::Cow::Owned(RenderOptions {
+            strokes_only: false,
+            wireframe: None,
+            ..self.clone()
+        })
+    }
+}
+
+impl std::fmt::Debug for RenderOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("RenderOptions")
+            .field("dash_gap_opacity", &self.dash_gap_opacity)
+            .field("draw_bboxes", &self.draw_bboxes)
+            .field("pixelated", &self.pixelated)
+            .field("checkerboard", &self.checkerboard)
+            .field("max_gradient_stops", &self.max_gradient_stops)
+            .field("paint_hook", &self.paint_hook.as_ref().map(|_| "Fn"))
+            .field("min_path_size", &self.min_path_size)
+            .field("flip_y", &self.flip_y)
+            .field("padding", &self.padding)
+            .field("strokes_only", &self.strokes_only)
+            .field("clip", &self.clip)
+            .field("view_box", &self.view_box)
+            .field("image_cache", &self.image_cache)
+            .field("gradient_bands", &self.gradient_bands)
+            .field("progress", &self.progress.as_ref().map(|_| "Fn"))
+            .field("supersample", &self.supersample)
+            .field("output_gamma", &self.output_gamma)
+            .field("turbulence_seed_offset", &self.turbulence_seed_offset)
+            .field("ignore_root_overflow", &self.ignore_root_overflow)
+            .field("wireframe", &self.wireframe)
+            .finish()
+    }
+}
+
+
+/// A `RenderOptions` builder.
+///
+/// Unset options are taken from `RenderOptions::default()`.
+#[derive(Clone, Default, Debug)]
+pub struct RenderOptionsBuilder {
+    opt: RenderOptions,
+}
+
+impl RenderOptionsBuilder {
+    /// Sets `RenderOptions::dash_gap_opacity`.
+    pub fn dash_gap_opacity(mut self, opacity: usvg::Opacity) -> Self {
+        self.opt.dash_gap_opacity = Some(opacity);
+        self
+    }
+
+    /// Sets `RenderOptions::draw_bboxes`.
+    pub fn draw_bboxes(mut self, flag: bool) -> Self {
+        self.opt.draw_bboxes = flag;
+        self
+    }
+
+    /// Sets `RenderOptions::pixelated`.
+    pub fn pixelated(mut self, flag: bool) -> Self {
+        self.opt.pixelated = flag;
+        self
+    }
+
+    /// Sets `RenderOptions::checkerboard`.
+    pub fn checkerboard(mut self, checkerboard: Checkerboard) -> Self {
+        self.opt.checkerboard = Some(checkerboard);
+        self
+    }
+
+    /// Sets `RenderOptions::max_gradient_stops`.
+    pub fn max_gradient_stops(mut self, count: usize) -> Self {
+        self.opt.max_gradient_stops = Some(count);
+        self
+    }
+
+    /// Sets `RenderOptions::paint_hook`.
+    pub fn paint_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&usvg::Node, PaintKind, &mut tiny_skia::Paint) + Send + Sync + 'static
+    {
+        self.opt.paint_hook = Some(std::sync::Arc::new(hook));
+        self
+    }
+
+    /// Sets `RenderOptions::min_path_size`.
+    pub fn min_path_size(mut self, size: f64) -> Self {
+        self.opt.min_path_size = Some(size);
+        self
+    }
+
+    /// Sets `RenderOptions::flip_y`.
+    pub fn flip_y(mut self, flag: bool) -> Self {
+        self.opt.flip_y = flag;
+        self
+    }
+
+    /// Sets `RenderOptions::padding`.
+    pub fn padding(mut self, padding: u32) -> Self {
+        self.opt.padding = padding;
+        self
+    }
+
+    /// Sets `RenderOptions::strokes_only`.
+    pub fn strokes_only(mut self, flag: bool) -> Self {
+        self.opt.strokes_only = flag;
+        self
+    }
+
+    /// Sets `RenderOptions::clip`.
+    pub fn clip(mut self, path: usvg::PathData) -> Self {
+        self.opt.clip = Some(path);
+        self
+    }
+
+    /// Sets `RenderOptions::view_box`.
+    pub fn view_box(mut self, rect: usvg::Rect) -> Self {
+        self.opt.view_box = Some(rect);
+        self
+    }
+
+    /// Sets `RenderOptions::image_cache`.
+    pub fn image_cache(mut self, cache: ImageCache) -> Self {
+        self.opt.image_cache = Some(cache);
+        self
+    }
+
+    /// Sets `RenderOptions::gradient_bands`.
+    pub fn gradient_bands(mut self, count: u32) -> Self {
+        self.opt.gradient_bands = Some(count);
+        self
+    }
+
+    /// Sets `RenderOptions::progress`.
+    pub fn progress<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(f32) + Send + Sync + 'static
+    {
+        self.opt.progress = Some(std::sync::Arc::new(hook));
+        self
+    }
+
+    /// Sets `RenderOptions::supersample`.
+    pub fn supersample(mut self, factor: u32) -> Self {
+        self.opt.supersample = Some(factor);
+        self
+    }
+
+    /// Sets `RenderOptions::output_gamma`.
+    pub fn output_gamma(mut self, gamma: f32) -> Self {
+        self.opt.output_gamma = Some(gamma);
+        self
+    }
+
+    /// Sets `RenderOptions::turbulence_seed_offset`.
+    pub fn turbulence_seed_offset(mut self, offset: i32) -> Self {
+        self.opt.turbulence_seed_offset = offset;
+        self
+    }
+
+    /// Sets `RenderOptions::ignore_root_overflow`.
+    pub fn ignore_root_overflow(mut self, flag: bool) -> Self {
+        self.opt.ignore_root_overflow = flag;
+        self
+    }
+
+    /// Sets `RenderOptions::wireframe`.
+    pub fn wireframe(mut self, color: usvg::Color) -> Self {
+        self.opt.wireframe = Some(color);
+        self
+    }
+
+    /// Builds `RenderOptions`.
+    pub fn build(self) -> RenderOptions {
+        self.opt
+    }
+}
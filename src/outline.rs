@@ -0,0 +1,375 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Stroke outlining.
+//!
+//! Skia doesn't expose its stroker, so strokes are outlined manually
+//! using flattened subpaths.
+
+use std::f64::consts::PI;
+
+#[derive(Clone, Copy, PartialEq)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+impl Point {
+    fn new(x: f64, y: f64) -> Self {
+        Point { x, y }
+    }
+
+    fn add(self, other: Point, scale: f64) -> Self {
+        Point::new(self.x + other.x * scale, self.y + other.y * scale)
+    }
+
+    fn distance(self, other: Point) -> f64 {
+        (other.x - self.x).hypot(other.y - self.y)
+    }
+
+    /// Returns a unit vector from `self` to `other`.
+    fn direction(self, other: Point) -> Self {
+        let len = self.distance(other);
+        Point::new((other.x - self.x) / len, (other.y - self.y) / len)
+    }
+
+    /// Returns a unit normal of a direction.
+    fn normal(self) -> Self {
+        Point::new(-self.y, self.x)
+    }
+}
+
+struct Polyline {
+    points: Vec<Point>,
+    closed: bool,
+    /// A direction of square caps when the polyline has zero length.
+    direction: Point,
+}
+
+/// Converts a stroke into a fillable path.
+///
+/// Curves are flattened using `tolerance` first. The outline is made of
+/// overlapping polygons with the same direction, so it should be filled
+/// using the `nonzero` rule.
+///
+/// `miter-clip` is outlined as `miter` and `arcs` as `round`, like during rendering.
+/// Returns an empty path when `tolerance` is not positive.
+pub fn stroke_to_path(
+    data: &usvg::PathData,
+    stroke: &usvg::Stroke,
+    tolerance: f64,
+) -> usvg::PathData {
+    let mut path = usvg::PathData::new();
+    if tolerance <= 0.0 || !tolerance.is_finite() {
+        return path;
+    }
+
+    let mut polylines = collect_polylines(&data.flatten(tolerance));
+    if let Some(ref list) = stroke.dasharray {
+        let keep_dots = stroke.linecap != usvg::LineCap::Butt;
+        polylines = apply_dashes(polylines, list, stroke.dashoffset as f64, keep_dots);
+    }
+
+    let half_width = stroke.width.value() / 2.0;
+    let circle_steps = circle_steps(half_width, tolerance);
+    for polyline in &polylines {
+        outline_polyline(polyline, stroke, half_width, circle_steps, &mut path);
+    }
+
+    path
+}
+
+/// Splits flattened path data into polylines.
+///
+/// Subpaths that consist only of a `MoveTo` are skipped,
+/// since they have no direction and are not stroked.
+fn collect_polylines(data: &usvg::PathData) -> Vec<Polyline> {
+    fn finish(points: &mut Vec<Point>, closed: bool, has_lines: bool, polylines: &mut Vec<Polyline>) {
+        if has_lines && !points.is_empty() {
+            if closed && points.len() > 1 && points.first() == points.last() {
+                points.pop();
+            }
+
+            polylines.push(Polyline { points: points.clone(), closed, direction: Point::new(1.0, 0.0) });
+        }
+
+        points.clear();
+    }
+
+    let mut polylines = Vec::new();
+    let mut points: Vec<Point> = Vec::new();
+    let mut start = Point::new(0.0, 0.0);
+    let mut has_lines = false;
+    for seg in data.iter() {
+        match *seg {
+            usvg::PathSegment::MoveTo { x, y } => {
+                finish(&mut points, false, has_lines, &mut polylines);
+                start = Point::new(x, y);
+                points.push(start);
+                has_lines = false;
+            }
+            usvg::PathSegment::LineTo { x, y } => {
+                // A segment after `ClosePath` starts from the subpath start.
+                if points.is_empty() {
+                    points.push(start);
+                }
+
+                let p = Point::new(x, y);
+                if points.last() != Some(&p) {
+                    points.push(p);
+                }
+
+                has_lines = true;
+            }
+            usvg::PathSegment::ClosePath => {
+                finish(&mut points, true, true, &mut polylines);
+            }
+            // `flatten` produces only lines.
+            usvg::PathSegment::CurveTo { .. } => {}
+        }
+    }
+
+    finish(&mut points, false, has_lines, &mut polylines);
+    polylines
+}
+
+/// Splits polylines into dashes.
+///
+/// Like in Skia, the dash pattern restarts at each subpath.
+/// Zero-length dashes are kept only when `keep_dots` is set,
+/// since they are painted only by caps.
+fn apply_dashes(polylines: Vec<Polyline>, list: &[f64], offset: f64, keep_dots: bool) -> Vec<Polyline> {
+    let total: f64 = list.iter().sum();
+    if total.is_nan() || total <= 0.0 || list.iter().any(|n| *n < 0.0) {
+        return polylines;
+    }
+
+    let mut dashes = Vec::new();
+    for polyline in polylines {
+        let mut points = polyline.points;
+        if polyline.closed {
+            points.push(points[0]);
+        }
+
+        // Find the dash at the start of the subpath.
+        let mut offset = offset % total;
+        if offset < 0.0 {
+            offset += total;
+        }
+
+        // A zero-length dash at the start is still a dash.
+        let mut idx = 0;
+        while offset > list[idx] || (offset == list[idx] && list[idx] > 0.0) {
+            offset -= list[idx];
+            idx = (idx + 1) % list.len();
+        }
+        let mut left = list[idx] - offset;
+
+        let mut dash = vec![points[0]];
+        let mut dir = Point::new(1.0, 0.0);
+        for pair in points.windows(2) {
+            let (p0, p1) = (pair[0], pair[1]);
+            let len = p0.distance(p1);
+            dir = p0.direction(p1);
+            let mut pos = 0.0;
+            while len - pos > left {
+                pos += left;
+                let p = p0.add(dir, pos);
+                if idx % 2 == 0 {
+                    dash.push(p);
+                    push_dash(&dash, dir, keep_dots, &mut dashes);
+                }
+
+                // Even indices are dashes and odd ones are gaps.
+                dash.clear();
+                dash.push(p);
+                idx = (idx + 1) % list.len();
+                left = list[idx];
+            }
+
+            left -= len - pos;
+            dash.push(p1);
+        }
+
+        if idx % 2 == 0 {
+            push_dash(&dash, dir, keep_dots, &mut dashes);
+        }
+    }
+
+    dashes
+}
+
+fn push_dash(points: &[Point], direction: Point, keep_dots: bool, dashes: &mut Vec<Polyline>) {
+    let mut points = points.to_vec();
+    points.dedup();
+    if points.len() > 1 || keep_dots {
+        dashes.push(Polyline { points, closed: false, direction });
+    }
+}
+
+fn outline_polyline(
+    polyline: &Polyline,
+    stroke: &usvg::Stroke,
+    half_width: f64,
+    circle_steps: usize,
+    path: &mut usvg::PathData,
+) {
+    let points = &polyline.points;
+
+    // A zero-length subpath is painted only by its caps.
+    if points.len() == 1 {
+        let p = points[0];
+        match stroke.linecap {
+            usvg::LineCap::Butt => {}
+            usvg::LineCap::Round => push_circle(p, half_width, circle_steps, path),
+            usvg::LineCap::Square => {
+                let dir = polyline.direction;
+                push_cap(p, dir, half_width, path);
+                push_cap(p, Point::new(-dir.x, -dir.y), half_width, path);
+            }
+        }
+
+        return;
+    }
+
+    let mut segments: Vec<(Point, Point)> = points.windows(2).map(|w| (w[0], w[1])).collect();
+    if polyline.closed {
+        segments.push((points[points.len() - 1], points[0]));
+    }
+
+    for &(p0, p1) in &segments {
+        let n = p0.direction(p1).normal();
+        push_polygon(&[
+            p0.add(n, half_width),
+            p1.add(n, half_width),
+            p1.add(n, -half_width),
+            p0.add(n, -half_width),
+        ], path);
+    }
+
+    for pair in segments.windows(2) {
+        push_join(pair[0], pair[1], stroke, half_width, circle_steps, path);
+    }
+
+    if polyline.closed {
+        push_join(segments[segments.len() - 1], segments[0], stroke, half_width, circle_steps, path);
+    } else {
+        let (first, last) = (segments[0], segments[segments.len() - 1]);
+        for &(p, dir) in &[(first.0, first.1.direction(first.0)), (last.1, last.0.direction(last.1))] {
+            match stroke.linecap {
+                usvg::LineCap::Butt => {}
+                usvg::LineCap::Round => push_circle(p, half_width, circle_steps, path),
+                usvg::LineCap::Square => push_cap(p, dir, half_width, path),
+            }
+        }
+    }
+}
+
+fn push_join(
+    seg0: (Point, Point),
+    seg1: (Point, Point),
+    stroke: &usvg::Stroke,
+    half_width: f64,
+    circle_steps: usize,
+    path: &mut usvg::PathData,
+) {
+    let p = seg0.1;
+    let d0 = seg0.0.direction(seg0.1);
+    let d1 = seg1.0.direction(seg1.1);
+    let cross = d0.x * d1.y - d0.y * d1.x;
+    let dot = d0.x * d1.x + d0.y * d1.y;
+
+    // Collinear segments do not need a join.
+    if cross.abs() < 1e-9 && dot > 0.0 {
+        return;
+    }
+
+    if let usvg::LineJoin::Round | usvg::LineJoin::Arcs = stroke.linejoin {
+        push_circle(p, half_width, circle_steps, path);
+        return;
+    }
+
+    // The join is on the outer side of the turn.
+    let side = if cross > 0.0 { -half_width } else { half_width };
+    let a = p.add(d0.normal(), side);
+    let b = p.add(d1.normal(), side);
+
+    if let usvg::LineJoin::Miter | usvg::LineJoin::MiterClip = stroke.linejoin {
+        // The ratio between the miter length and the stroke width.
+        let ratio = 1.0 / ((1.0 + dot) / 2.0).sqrt();
+        if ratio.is_finite() && ratio <= stroke.miterlimit.value() {
+            let n0 = d0.normal();
+            let n1 = d1.normal();
+            let bisector = Point::new(n0.x + n1.x, n0.y + n1.y);
+            let len = bisector.x.hypot(bisector.y);
+            let m = p.add(Point::new(bisector.x / len, bisector.y / len), side * ratio);
+            push_polygon(&[p, a, m, b], path);
+            return;
+        }
+    }
+
+    push_polygon(&[p, a, b], path);
+}
+
+/// Adds a square cap, which extends the stroke by `half_width` in the `dir` direction.
+fn push_cap(p: Point, dir: Point, half_width: f64, path: &mut usvg::PathData) {
+    let n = dir.normal();
+    let p2 = p.add(dir, half_width);
+    push_polygon(&[
+        p.add(n, half_width),
+        p2.add(n, half_width),
+        p2.add(n, -half_width),
+        p.add(n, -half_width),
+    ], path);
+}
+
+fn push_circle(center: Point, radius: f64, steps: usize, path: &mut usvg::PathData) {
+    let points: Vec<Point> = (0..steps).map(|i| {
+        let a = 2.0 * PI * i as f64 / steps as f64;
+        Point::new(center.x + radius * a.cos(), center.y + radius * a.sin())
+    }).collect();
+
+    push_polygon(&points, path);
+}
+
+/// Returns the number of segments that approximate a circle within `tolerance`.
+fn circle_steps(radius: f64, tolerance: f64) -> usize {
+    if tolerance >= radius {
+        return 8;
+    }
+
+    let steps = (PI / (1.0 - tolerance / radius).acos()).ceil();
+    (steps as usize).clamp(8, 256)
+}
+
+/// Adds a polygon with a positive signed area.
+///
+/// Degenerate polygons are skipped.
+fn push_polygon(points: &[Point], path: &mut usvg::PathData) {
+    let mut area = 0.0;
+    for (i, p0) in points.iter().enumerate() {
+        let p1 = points[(i + 1) % points.len()];
+        area += p0.x * p1.y - p1.x * p0.y;
+    }
+
+    if area.is_nan() || area.abs() <= 1e-12 {
+        return;
+    }
+
+    let mut iter: Box<dyn Iterator<Item = &Point>> = if area > 0.0 {
+        Box::new(points.iter())
+    } else {
+        Box::new(points.iter().rev())
+    };
+
+    if let Some(p) = iter.next() {
+        path.push_move_to(p.x, p.y);
+    }
+
+    for p in iter {
+        path.push_line_to(p.x, p.y);
+    }
+
+    path.push_close_path();
+}
@@ -4,17 +4,25 @@
 
 use crate::render::prelude::*;
 
+/// Creates a fill paint.
+///
+/// Returns `None` when the fill is fully transparent and shouldn't be drawn.
 pub fn fill(
     tree: &usvg::Tree,
     fill: &Option<usvg::Fill>,
+    opt: &RenderOptions,
     bbox: Rect,
     global_ts: usvg::Transform,
-) -> skia::Paint {
+) -> Option<skia::Paint> {
     let mut paint = skia::Paint::new();
     paint.set_style(skia::PaintStyle::Fill);
 
     if let Some(ref fill) = fill {
         let opacity = fill.opacity;
+        if opacity.value().is_fuzzy_zero() {
+            return None;
+        }
+
         match fill.paint {
             usvg::Paint::Color(c) => {
                 paint.set_color(c.red, c.green, c.blue, opacity.to_u8());
@@ -23,13 +31,13 @@ pub fn fill(
                 if let Some(node) = tree.defs_by_id(id) {
                     match *node.borrow() {
                         usvg::NodeKind::LinearGradient(ref lg) => {
-                            prepare_linear(lg, opacity, bbox, &mut paint);
+                            prepare_linear(lg, opt, opacity, bbox, &mut paint);
                         }
                         usvg::NodeKind::RadialGradient(ref rg) => {
-                            prepare_radial(rg, opacity, bbox, &mut paint);
+                            prepare_radial(rg, opt, opacity, bbox, &mut paint);
                         }
                         usvg::NodeKind::Pattern(ref pattern) => {
-                            prepare_pattern(&node, pattern, global_ts, bbox, opacity, &mut paint);
+                            prepare_pattern(&node, pattern, opt, global_ts, bbox, opacity, &mut paint);
                         }
                         _ => {}
                     }
@@ -38,20 +46,28 @@ pub fn fill(
         }
     }
 
-    paint
+    Some(paint)
 }
 
+/// Creates a stroke paint.
+///
+/// Returns `None` when the stroke is fully transparent and shouldn't be drawn.
 pub fn stroke(
     tree: &usvg::Tree,
     stroke: &Option<usvg::Stroke>,
+    opt: &RenderOptions,
     bbox: Rect,
     global_ts: usvg::Transform,
-) -> skia::Paint {
+) -> Option<skia::Paint> {
     let mut paint = skia::Paint::new();
     paint.set_style(skia::PaintStyle::Stroke);
 
     if let Some(ref stroke) = stroke {
         let opacity = stroke.opacity;
+        if opacity.value().is_fuzzy_zero() {
+            return None;
+        }
+
         match stroke.paint {
             usvg::Paint::Color(c) => {
                 paint.set_color(c.red, c.green, c.blue, opacity.to_u8());
@@ -60,13 +76,13 @@ pub fn stroke(
                 if let Some(node) = tree.defs_by_id(id) {
                     match *node.borrow() {
                         usvg::NodeKind::LinearGradient(ref lg) => {
-                            prepare_linear(lg, opacity, bbox, &mut paint);
+                            prepare_linear(lg, opt, opacity, bbox, &mut paint);
                         }
                         usvg::NodeKind::RadialGradient(ref rg) => {
-                            prepare_radial(rg, opacity, bbox, &mut paint);
+                            prepare_radial(rg, opt, opacity, bbox, &mut paint);
                         }
                         usvg::NodeKind::Pattern(ref pattern) => {
-                            prepare_pattern(&node, pattern, global_ts, bbox, opacity, &mut paint);
+                            prepare_pattern(&node, pattern, opt, global_ts, bbox, opacity, &mut paint);
                         }
                         _ => {}
                     }
@@ -74,43 +90,65 @@ pub fn stroke(
             }
         }
 
-        let stroke_cap = match stroke.linecap {
-            usvg::LineCap::Butt => skia::StrokeCap::Butt,
-            usvg::LineCap::Round => skia::StrokeCap::Round,
-            usvg::LineCap::Square => skia::StrokeCap::Square,
-        };
-        paint.set_stroke_cap(stroke_cap);
-
-        let stroke_join = match stroke.linejoin {
-            usvg::LineJoin::Miter => skia::StrokeJoin::Miter,
-            usvg::LineJoin::Round => skia::StrokeJoin::Round,
-            usvg::LineJoin::Bevel => skia::StrokeJoin::Bevel,
-        };
-        paint.set_stroke_join(stroke_join);
-
-        paint.set_stroke_miter(stroke.miterlimit.value() as f32);
-        paint.set_stroke_width(stroke.width.value() as f32);
-
-        if let Some(ref list) = stroke.dasharray {
-            let list: Vec<_> = list.iter().map(|n| *n as f32).collect();
-            let path_effect = skia::PathEffect::new_dash_path(&list, stroke.dashoffset).unwrap();
-            paint.set_path_effect(&path_effect);
-        }
+        set_stroke_style(stroke, &mut paint);
     }
 
-    paint
+    Some(paint)
+}
+
+/// Sets the stroke width, cap, join, miter limit and dashing.
+pub fn set_stroke_style(stroke: &usvg::Stroke, paint: &mut skia::Paint) {
+    let stroke_cap = match stroke.linecap {
+        usvg::LineCap::Butt => skia::StrokeCap::Butt,
+        usvg::LineCap::Round => skia::StrokeCap::Round,
+        usvg::LineCap::Square => skia::StrokeCap::Square,
+    };
+    paint.set_stroke_cap(stroke_cap);
+
+    // Skia doesn't support SVG 2 joins, so we have to approximate them.
+    // `miter-clip` is rendered as `miter`, which falls back to `bevel` past the limit,
+    // and `arcs` as `round`.
+    let stroke_join = match stroke.linejoin {
+        usvg::LineJoin::Miter => skia::StrokeJoin::Miter,
+        usvg::LineJoin::MiterClip => skia::StrokeJoin::Miter,
+        usvg::LineJoin::Round => skia::StrokeJoin::Round,
+        usvg::LineJoin::Bevel => skia::StrokeJoin::Bevel,
+        usvg::LineJoin::Arcs => skia::StrokeJoin::Round,
+    };
+    paint.set_stroke_join(stroke_join);
+
+    paint.set_stroke_miter(stroke.miterlimit.value() as f32);
+    paint.set_stroke_width(stroke.width.value() as f32);
+
+    if let Some(ref list) = stroke.dasharray {
+        let list: Vec<_> = list.iter().map(|n| *n as f32).collect();
+        let path_effect = skia::PathEffect::new_dash_path(&list, stroke.dashoffset).unwrap();
+        paint.set_path_effect(&path_effect);
+    }
 }
 
 fn prepare_linear(
     g: &usvg::LinearGradient,
+    opt: &RenderOptions,
     opacity: usvg::Opacity,
     bbox: Rect,
     paint: &mut skia::Paint,
 ) {
+    // A zero-length gradient vector is painted using the last stop.
+    // usvg already handles this, but a tree can be created manually.
+    if g.x1.fuzzy_eq(&g.x2) && g.y1.fuzzy_eq(&g.y2) {
+        if let Some(stop) = g.stops.last() {
+            let a = stop.opacity * opacity;
+            paint.set_color(stop.color.red, stop.color.green, stop.color.blue, a.to_u8());
+        }
+
+        return;
+    }
+
     let gradient = skia::LinearGradient {
         start_point: (g.x1 as f32, g.y1 as f32),
         end_point: (g.x2 as f32, g.y2 as f32),
-        base: prepare_base_gradient(g, opacity, &bbox)
+        base: prepare_base_gradient(g, opt, opacity, &bbox)
     };
 
     let shader = skia::Shader::new_linear_gradient(&gradient).unwrap();
@@ -119,6 +157,7 @@ fn prepare_linear(
 
 fn prepare_radial(
     g: &usvg::RadialGradient,
+    opt: &RenderOptions,
     opacity: usvg::Opacity,
     bbox: Rect,
     paint: &mut skia::Paint,
@@ -129,7 +168,7 @@ fn prepare_radial(
         start_radius: 0.0,
         end: (g.cx as f32, g.cy as f32),
         end_radius: g.r.value() as f32,
-        base: prepare_base_gradient(g, opacity, &bbox)
+        base: prepare_base_gradient(g, opt, opacity, &bbox)
     };
 
     let shader = skia::Shader::new_two_point_conical_gradient(&gradient).unwrap();
@@ -138,6 +177,7 @@ fn prepare_radial(
 
 fn prepare_base_gradient(
     g: &usvg::BaseGradient,
+    opt: &RenderOptions,
     opacity: usvg::Opacity,
     bbox: &Rect
 ) -> skia::Gradient {
@@ -161,7 +201,27 @@ fn prepare_base_gradient(
     let mut colors = Vec::new();
     let mut positions = Vec::new();
 
-    for stop in &g.stops {
+    let simplified;
+    let stops = match opt.max_gradient_stops {
+        Some(max) if g.stops.len() > max => {
+            let mut stops = g.stops.clone();
+            simplify_stops(&mut stops, max);
+            simplified = stops;
+            &simplified
+        }
+        _ => &g.stops,
+    };
+
+    let banded;
+    let stops = match opt.gradient_bands {
+        Some(count) => {
+            banded = band_stops(stops, count);
+            &banded
+        }
+        None => stops,
+    };
+
+    for stop in stops {
         let a = stop.opacity * opacity;
         let color = skia::Color::new(a.to_u8(), stop.color.red, stop.color.green, stop.color.blue);
         colors.push(color);
@@ -171,9 +231,108 @@ fn prepare_base_gradient(
     skia::Gradient { colors, positions, tile_mode, transform }
 }
 
+/// Removes stops until no more than `max` are left.
+///
+/// On each step removes a stop that is the closest to the interpolation
+/// of its neighbors. The first and the last stops are always preserved.
+fn simplify_stops(stops: &mut Vec<usvg::Stop>, max: usize) {
+    fn components(stop: &usvg::Stop) -> [f64; 4] {
+        [
+            stop.color.red as f64,
+            stop.color.green as f64,
+            stop.color.blue as f64,
+            stop.opacity.value() * 255.0,
+        ]
+    }
+
+    let max = std::cmp::max(max, 2);
+    while stops.len() > max {
+        let mut min_idx = 1;
+        let mut min_dist = f64::MAX;
+        for i in 1..stops.len() - 1 {
+            let prev = &stops[i - 1];
+            let next = &stops[i + 1];
+
+            let range = next.offset.value() - prev.offset.value();
+            let t = if range.is_fuzzy_zero() {
+                0.0
+            } else {
+                (stops[i].offset.value() - prev.offset.value()) / range
+            };
+
+            let c1 = components(prev);
+            let c2 = components(next);
+            let c = components(&stops[i]);
+
+            let mut dist = 0.0;
+            for n in 0..4 {
+                let d = c1[n] + (c2[n] - c1[n]) * t - c[n];
+                dist += d * d;
+            }
+
+            if dist < min_dist {
+                min_dist = dist;
+                min_idx = i;
+            }
+        }
+
+        stops.remove(min_idx);
+    }
+}
+
+/// Replaces stops with `count` solid color bands.
+///
+/// Each band has a color of the original gradient at the band center.
+fn band_stops(stops: &[usvg::Stop], count: u32) -> Vec<usvg::Stop> {
+    if stops.is_empty() {
+        return Vec::new();
+    }
+
+    let count = std::cmp::max(count, 1);
+    let mut banded = Vec::with_capacity(count as usize * 2);
+    for i in 0..count {
+        let start = i as f64 / count as f64;
+        let end = (i + 1) as f64 / count as f64;
+        let stop = sample_stops(stops, (start + end) / 2.0);
+
+        // Stops with the same offset produce a hard edge.
+        banded.push(usvg::Stop { offset: start.into(), ..stop });
+        banded.push(usvg::Stop { offset: end.into(), ..stop });
+    }
+
+    banded
+}
+
+/// Returns an interpolated stop at the `t` offset.
+fn sample_stops(stops: &[usvg::Stop], t: f64) -> usvg::Stop {
+    let idx = match stops.iter().position(|s| s.offset.value() >= t) {
+        Some(0) => return stops[0],
+        Some(idx) => idx,
+        None => return stops[stops.len() - 1],
+    };
+
+    let prev = &stops[idx - 1];
+    let next = &stops[idx];
+
+    let range = next.offset.value() - prev.offset.value();
+    let k = if range.is_fuzzy_zero() { 1.0 } else { (t - prev.offset.value()) / range };
+    let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * k).round() as u8;
+
+    usvg::Stop {
+        offset: t.into(),
+        color: usvg::Color::new(
+            mix(prev.color.red, next.color.red),
+            mix(prev.color.green, next.color.green),
+            mix(prev.color.blue, next.color.blue),
+        ),
+        opacity: (prev.opacity.value() + (next.opacity.value() - prev.opacity.value()) * k).into(),
+    }
+}
+
 fn prepare_pattern(
     pattern_node: &usvg::Node,
     pattern: &usvg::Pattern,
+    opt: &RenderOptions,
     global_ts: usvg::Transform,
     bbox: Rect,
     opacity: usvg::Opacity,
@@ -185,9 +344,21 @@ fn prepare_pattern(
         pattern.rect
     };
 
-    let (sx, sy) = global_ts.get_scale();
+    let (mut sx, mut sy) = global_ts.get_scale();
+
+    // Render the tile at the resolution it will be sampled at when `patternTransform`
+    // downscales it. The surface shader doesn't use mipmaps,
+    // so a detailed tile will be aliased otherwise.
+    let (ts_sx, ts_sy) = pattern.transform.get_scale();
+    if ts_sx < 1.0 {
+        sx *= ts_sx;
+    }
+    if ts_sy < 1.0 {
+        sy *= ts_sy;
+    }
 
     let img_size = try_opt!(Size::new(r.width() * sx, r.height() * sy)).to_screen_size();
+
     let mut surface = try_opt!(crate::render::create_subsurface(img_size));
     surface.clear();
 
@@ -204,7 +375,13 @@ fn prepare_pattern(
     }
 
     let mut layers = Layers::new(img_size);
-    crate::render::render_group(pattern_node, &mut RenderState::Ok, &mut layers, &mut surface);
+    // A pattern tile is a paint and not a geometry, so it must be rendered as is.
+    let mut opt = opt.for_content();
+    if opt.flip_y {
+        // The tile is flipped along with the canvas it's drawn onto.
+        opt.to_mut().flip_y = false;
+    }
+    crate::render::render_group(pattern_node, &opt, &mut RenderState::Ok, &mut layers, &mut surface);
 
     let mut ts = usvg::Transform::default();
     ts.append(&pattern.transform);
@@ -3,22 +3,51 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 use crate::render::prelude::*;
+use crate::PaintKind;
 
+/// Draws a path.
+///
+/// `node` is passed to `RenderOptions::paint_hook`. `None` disables the hook,
+/// `RenderOptions::strokes_only` and `RenderOptions::wireframe`, which is used by clip paths.
 pub fn draw(
     tree: &usvg::Tree,
+    node: Option<&usvg::Node>,
     path: &usvg::Path,
+    opt: &RenderOptions,
     blend_mode: skia::BlendMode,
     canvas: &mut skia::Canvas,
 ) -> Option<Rect> {
-    let bbox = path.data.bbox();
+    let path_bbox = path.data.bbox();
+
+    if let (Some(color), Some(_)) = (opt.wireframe, node) {
+        draw_wireframe(path, color, canvas);
+        return path_bbox;
+    }
+
+    let curr_ts = canvas.get_transform();
+    let mut bbox = path_bbox;
+    if path.vector_effect == usvg::VectorEffect::NonScalingSize {
+        // Remove the scale of the current transform,
+        // so the path will have the same size regardless of the zoom.
+        // The stroke is scaled along with the path, so the bbox is scaled too.
+        // Markers are separate paths and are not affected.
+        let (sx, sy) = usvg::Transform::from_native(curr_ts).get_scale();
+        if !sx.is_fuzzy_zero() && !sy.is_fuzzy_zero() {
+            let ts = usvg::Transform::new_scale(1.0 / sx, 1.0 / sy);
+            canvas.concat(ts.to_native());
+            bbox = bbox.and_then(|r| r.transform(&ts));
+        }
+    }
+
     if path.visibility != usvg::Visibility::Visible {
+        canvas.set_transform(curr_ts);
         return bbox;
     }
 
     // `usvg` guaranties that path without a bbox will not use
     // a paint server with ObjectBoundingBox,
     // so we can pass whatever rect we want, because it will not be used anyway.
-    let style_bbox = bbox.unwrap_or_else(|| Rect::new(0.0, 0.0, 1.0, 1.0).unwrap());
+    let style_bbox = path_bbox.unwrap_or_else(|| Rect::new(0.0, 0.0, 1.0, 1.0).unwrap());
 
     let mut skia_path = convert_path(&path.data);
     if let Some(ref fill) = path.fill {
@@ -27,28 +56,262 @@ pub fn draw(
         }
     };
 
-    let antialias = path.rendering_mode.use_shape_antialiasing();
+    // Supersampled images are anti-aliased by scaling down.
+    let antialias = path.rendering_mode.use_shape_antialiasing()
+        && crate::supersample_factor(opt).is_none();
 
     let global_ts = usvg::Transform::from_native(canvas.get_transform());
 
-    if path.fill.is_some() {
-        let mut fill = crate::paint_server::fill(tree, &path.fill, style_bbox, global_ts);
-        fill.set_anti_alias(antialias);
-        fill.set_blend_mode(blend_mode);
-        canvas.draw_path(&skia_path, &fill);
+    if let Some(min_size) = opt.min_path_size {
+        if is_too_small(path, global_ts, min_size) {
+            canvas.set_transform(curr_ts);
+            return bbox;
+        }
     }
 
-    if path.stroke.is_some() {
-        let mut stroke = crate::paint_server::stroke(tree, &path.stroke, style_bbox, global_ts);
-        stroke.set_anti_alias(antialias);
-        stroke.set_blend_mode(blend_mode);
-        canvas.draw_path(&skia_path, &stroke);
+    let skip_fill = opt.strokes_only && node.is_some();
+    let fill_path = |canvas: &mut skia::Canvas| {
+        if path.fill.is_some() && !skip_fill {
+            let mut fill = match crate::paint_server::fill(tree, &path.fill, opt, style_bbox, global_ts) {
+                Some(fill) => fill,
+                None => return,
+            };
+            fill.set_anti_alias(antialias);
+            fill.set_blend_mode(blend_mode);
+            apply_paint_hook(node, PaintKind::Fill, opt, &mut fill);
+            canvas.draw_path(&skia_path, &fill);
+        }
+    };
+
+    // Axis-aligned `crispEdges` strokes are aligned to the pixel grid and get
+    // a whole number of pixels width, otherwise they can be smeared
+    // across two pixels even without anti-aliasing.
+    let snapped = match path.stroke {
+        Some(ref stroke) if path.rendering_mode == usvg::ShapeRendering::CrispEdges => {
+            snap_to_pixel_grid(&path.data, stroke, global_ts)
+                .map(|(data, stroke)| (convert_path(&data), stroke))
+        }
+        _ => None,
+    };
+    let (stroke_skia_path, path_stroke) = match snapped {
+        Some((ref skia_path, ref stroke)) => (skia_path, Some(stroke)),
+        None => (&skia_path, path.stroke.as_ref()),
+    };
+
+    let stroke_path = |canvas: &mut skia::Canvas| {
+        if let Some(stroke) = path_stroke {
+            let strokes = match opt.dash_gap_opacity {
+                Some(gap_opacity) if stroke.dasharray.is_some() => split_dashed_stroke(stroke, gap_opacity),
+                _ => (Some(stroke.clone()), None),
+            };
+
+            for stroke in [strokes.0, strokes.1].iter().filter(|s| s.is_some()) {
+                let mut stroke = match crate::paint_server::stroke(tree, stroke, opt, style_bbox, global_ts) {
+                    Some(stroke) => stroke,
+                    None => continue,
+                };
+                stroke.set_anti_alias(antialias);
+                stroke.set_blend_mode(blend_mode);
+                apply_paint_hook(node, PaintKind::Stroke, opt, &mut stroke);
+                canvas.draw_path(stroke_skia_path, &stroke);
+            }
+        }
+    };
+
+    match path.paint_order {
+        usvg::PaintOrder::FillAndStroke => {
+            fill_path(canvas);
+            stroke_path(canvas);
+        }
+        usvg::PaintOrder::StrokeAndFill => {
+            stroke_path(canvas);
+            fill_path(canvas);
+        }
     }
 
+    canvas.set_transform(curr_ts);
+
     bbox
 }
 
-fn convert_path(
+/// Draws a path outline using a hairline.
+fn draw_wireframe(path: &usvg::Path, color: usvg::Color, canvas: &mut skia::Canvas) {
+    let mut paint = skia::Paint::new();
+    paint.set_style(skia::PaintStyle::Stroke);
+    paint.set_color(color.red, color.green, color.blue, 255);
+    paint.set_anti_alias(true);
+    // A zero width stroke is a hairline in Skia.
+    paint.set_stroke_width(0.0);
+    canvas.draw_path(&convert_path(&path.data), &paint);
+}
+
+/// Checks that a path bounding box in device pixels is smaller than `min_size`.
+///
+/// Paths without a bounding box are never skipped.
+fn is_too_small(path: &usvg::Path, ts: usvg::Transform, min_size: f64) -> bool {
+    match path.data.bbox_with_transform(ts, path.stroke.as_ref()) {
+        Some(r) => r.width() < min_size && r.height() < min_size,
+        None => false,
+    }
+}
+
+/// Aligns an axis-aligned path stroke to the pixel grid.
+///
+/// The stroke width is rounded to a whole number of pixels and the path edges are moved,
+/// so the stroke edges will lie on the pixel grid. Only the coordinate perpendicular
+/// to an edge is changed, so the ends of a horizontal line stay in place,
+/// while a corner is moved in both directions.
+///
+/// Returns `None` when the path has curves or diagonal segments,
+/// or when the transform has a rotation, a skew or a non-uniform scale.
+fn snap_to_pixel_grid(
+    data: &usvg::PathData,
+    stroke: &usvg::Stroke,
+    ts: usvg::Transform,
+) -> Option<(usvg::PathData, usvg::Stroke)> {
+    let scale = ts.a.abs();
+    if !ts.b.is_fuzzy_zero() || !ts.c.is_fuzzy_zero()
+        || scale.is_fuzzy_zero() || !scale.fuzzy_eq(&ts.d.abs())
+    {
+        return None;
+    }
+
+    // Each point is stored along with flags for snapping its `x` and `y`.
+    let mut points: Vec<(f64, f64, bool, bool)> = Vec::with_capacity(data.len());
+
+    // Marks the coordinates perpendicular to the edge between two points.
+    let mark_edge = |points: &mut Vec<(f64, f64, bool, bool)>, i1: usize, i2: usize| {
+        let (x1, y1, _, _) = points[i1];
+        let (x2, y2, _, _) = points[i2];
+        let (vertical, horizontal) = (x1.fuzzy_eq(&x2), y1.fuzzy_eq(&y2));
+        if vertical && horizontal {
+            return true;
+        }
+
+        for &i in &[i1, i2] {
+            if vertical {
+                points[i].2 = true;
+            } else if horizontal {
+                points[i].3 = true;
+            }
+        }
+
+        vertical || horizontal
+    };
+
+    let mut start = 0;
+    for seg in data.iter() {
+        match *seg {
+            usvg::PathSegment::MoveTo { x, y } => {
+                start = points.len();
+                points.push((x, y, false, false));
+            }
+            usvg::PathSegment::LineTo { x, y } => {
+                points.push((x, y, false, false));
+                let i = points.len() - 1;
+                if i == 0 || !mark_edge(&mut points, i - 1, i) {
+                    return None;
+                }
+            }
+            usvg::PathSegment::CurveTo { .. } => {
+                return None;
+            }
+            usvg::PathSegment::ClosePath => {
+                let last = points.len().checked_sub(1)?;
+                if !mark_edge(&mut points, last, start) {
+                    return None;
+                }
+            }
+        }
+    }
+
+    let width = (stroke.width.value() * scale).round().max(1.0);
+
+    // A stroke edge is half of the stroke width away from the path.
+    let half = width / 2.0;
+    let snap = |v: f64, k: f64, offset: f64| ((k * v + offset - half).round() + half - offset) / k;
+
+    let mut points = points.into_iter();
+    let mut next_point = || {
+        let (x, y, snap_x, snap_y) = points.next().unwrap();
+        (
+            if snap_x { snap(x, ts.a, ts.e) } else { x },
+            if snap_y { snap(y, ts.d, ts.f) } else { y },
+        )
+    };
+
+    let mut snapped = usvg::PathData::with_capacity(data.len());
+    for seg in data.iter() {
+        match *seg {
+            usvg::PathSegment::MoveTo { .. } => {
+                let (x, y) = next_point();
+                snapped.push_move_to(x, y);
+            }
+            usvg::PathSegment::LineTo { .. } => {
+                let (x, y) = next_point();
+                snapped.push_line_to(x, y);
+            }
+            usvg::PathSegment::CurveTo { .. } => {}
+            usvg::PathSegment::ClosePath => {
+                snapped.push_close_path();
+            }
+        }
+    }
+
+    let stroke = usvg::Stroke {
+        width: usvg::StrokeWidth::new(width / scale),
+        ..stroke.clone()
+    };
+
+    Some((snapped, stroke))
+}
+
+fn apply_paint_hook(
+    node: Option<&usvg::Node>,
+    kind: PaintKind,
+    opt: &RenderOptions,
+    paint: &mut skia::Paint,
+) {
+    if let (Some(node), Some(hook)) = (node, &opt.paint_hook) {
+        hook(node, kind, paint);
+    }
+}
+
+/// Splits a dashed stroke into two passes to emulate semi-transparent gaps.
+///
+/// Skia's dash path effect can only produce fully transparent gaps,
+/// so instead, the whole stroke is rendered with an opacity reduced by `gap_opacity`
+/// and then the dash pattern is drawn on top of it.
+///
+/// The dash pass opacity is adjusted in a way that the resulting dash opacity,
+/// after being composited over the first pass, will be equal to the original one.
+fn split_dashed_stroke(
+    stroke: &usvg::Stroke,
+    gap_opacity: usvg::Opacity,
+) -> (Option<usvg::Stroke>, Option<usvg::Stroke>) {
+    let opacity = stroke.opacity.value();
+    let gap_opacity = opacity * gap_opacity.value();
+
+    let solid_stroke = usvg::Stroke {
+        dasharray: None,
+        opacity: gap_opacity.into(),
+        .. stroke.clone()
+    };
+
+    // The gaps are fully opaque, so there is nothing to draw on top.
+    if gap_opacity.fuzzy_eq(&1.0) {
+        return (Some(solid_stroke), None);
+    }
+
+    let dash_stroke = usvg::Stroke {
+        opacity: ((opacity - gap_opacity) / (1.0 - gap_opacity)).into(),
+        .. stroke.clone()
+    };
+
+    (Some(solid_stroke), Some(dash_stroke))
+}
+
+pub fn convert_path(
     path: &usvg::PathData,
 ) -> skia::Path {
     let mut s_path = skia::Path::new();
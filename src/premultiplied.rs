@@ -0,0 +1,80 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{RenderOptions, ScreenSize};
+
+/// A raster image that owns the rendering surface.
+///
+/// Unlike `Image`, the data is not copied nor demultiplied,
+/// so it can be uploaded to a GPU or an OS surface directly.
+///
+/// Pixels are stored as premultiplied RGBA, one byte per channel,
+/// in the `R, G, B, A` byte order regardless of the platform endianness.
+/// Rows are stored top to bottom and each row starts at a `stride` byte offset,
+/// which can be bigger than `width * 4`.
+pub struct PremultipliedImage {
+    surface: tiny_skia::Surface,
+}
+
+impl PremultipliedImage {
+    /// Returns the image width.
+    ///
+    /// Never zero.
+    pub fn width(&self) -> u32 {
+        self.surface.width()
+    }
+
+    /// Returns the image height.
+    ///
+    /// Never zero.
+    pub fn height(&self) -> u32 {
+        self.surface.height()
+    }
+
+    /// Returns the image size.
+    pub fn size(&self) -> ScreenSize {
+        ScreenSize::new(self.width(), self.height()).unwrap()
+    }
+
+    /// Returns the number of bytes between the starts of two consecutive rows.
+    pub fn stride(&self) -> usize {
+        self.surface.data().len() / self.height() as usize
+    }
+
+    /// Returns the image content as `u8` slice.
+    ///
+    /// The slice length is `stride * height`.
+    pub fn data(&self) -> &[u8] {
+        self.surface.data_u8()
+    }
+
+    /// Returns the underlying surface.
+    pub fn take(self) -> tiny_skia::Surface {
+        self.surface
+    }
+}
+
+impl std::fmt::Debug for PremultipliedImage {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("PremultipliedImage")
+            .field("width", &self.width())
+            .field("height", &self.height())
+            .field("stride", &self.stride())
+            .finish()
+    }
+}
+
+/// Renders an SVG to a premultiplied image using the provided rendering options.
+///
+/// See `PremultipliedImage` for the data layout.
+pub fn render_premultiplied(
+    tree: &usvg::Tree,
+    fit_to: usvg::FitTo,
+    background: Option<usvg::Color>,
+    opt: &RenderOptions,
+) -> Option<PremultipliedImage> {
+    let (view_box, size) = crate::root_view_box(tree, opt);
+    let surface = crate::render_root(&tree.root(), view_box, size, fit_to, background, opt)?;
+    Some(PremultipliedImage { surface })
+}
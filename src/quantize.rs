@@ -0,0 +1,235 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use rgb::{FromSlice, RGBA8};
+
+use crate::Image;
+
+/// A dithering method.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Dithering {
+    /// Maps each pixel to the nearest palette color.
+    None,
+    /// Floyd–Steinberg error diffusion.
+    FloydSteinberg,
+    /// Ordered dithering using a 4x4 Bayer matrix.
+    Ordered,
+}
+
+/// An image with an indexed palette.
+#[derive(Clone, Debug)]
+pub struct IndexedImage {
+    /// Image width.
+    pub width: u32,
+
+    /// Image height.
+    pub height: u32,
+
+    /// A list of colors.
+    pub palette: Vec<RGBA8>,
+
+    /// Palette indices. One byte per pixel.
+    pub data: Vec<u8>,
+}
+
+const BAYER_4X4: [[u8; 4]; 4] = [
+    [ 0,  8,  2, 10],
+    [12,  4, 14,  6],
+    [ 3, 11,  1,  9],
+    [15,  7, 13,  5],
+];
+
+impl Image {
+    /// Converts the image into an indexed one using the provided palette.
+    ///
+    /// Transparent pixels are composited over white first,
+    /// since the target devices usually don't support transparency.
+    /// Colors are matched by RGB only.
+    ///
+    /// Returns `None` when the palette is empty or has more than 256 colors.
+    pub fn quantize(&self, palette: &[RGBA8], dithering: Dithering) -> Option<IndexedImage> {
+        if palette.is_empty() || palette.len() > 256 {
+            return None;
+        }
+
+        let width = self.width() as usize;
+        let height = self.height() as usize;
+
+        let mut pixels: Vec<[f32; 3]> = self.data().as_rgba().iter().map(flatten).collect();
+        let mut data = vec![0; width * height];
+
+        // Ordered dithering threshold amplitude.
+        let spread = 255.0 / std::cmp::max(palette.len() - 1, 1) as f32;
+
+        for y in 0..height {
+            for x in 0..width {
+                let idx = y * width + x;
+                let mut c = pixels[idx];
+
+                if dithering == Dithering::Ordered {
+                    let t = (BAYER_4X4[y & 3][x & 3] as f32 + 0.5) / 16.0 - 0.5;
+                    for v in &mut c {
+                        *v += t * spread;
+                    }
+                }
+
+                let color_idx = nearest_color(palette, c);
+                data[idx] = color_idx as u8;
+
+                if dithering == Dithering::FloydSteinberg {
+                    let p = palette[color_idx];
+                    let err = [c[0] - p.r as f32, c[1] - p.g as f32, c[2] - p.b as f32];
+
+                    let mut diffuse = |dx: isize, dy: usize, k: f32| {
+                        let nx = x as isize + dx;
+                        let ny = y + dy;
+                        if nx >= 0 && (nx as usize) < width && ny < height {
+                            let n = &mut pixels[ny * width + nx as usize];
+                            for i in 0..3 {
+                                n[i] += err[i] * k;
+                            }
+                        }
+                    };
+
+                    diffuse( 1, 0, 7.0 / 16.0);
+                    diffuse(-1, 1, 3.0 / 16.0);
+                    diffuse( 0, 1, 5.0 / 16.0);
+                    diffuse( 1, 1, 1.0 / 16.0);
+                }
+            }
+        }
+
+        Some(IndexedImage {
+            width: self.width(),
+            height: self.height(),
+            palette: palette.to_vec(),
+            data,
+        })
+    }
+
+    /// Converts the image into a packed 1-bit black and white one.
+    ///
+    /// Each row starts at a byte boundary. The most significant bit is the leftmost pixel.
+    /// A set bit indicates a black pixel, like in the PBM format.
+    pub fn to_monochrome(&self, dithering: Dithering) -> Vec<u8> {
+        let palette = [RGBA8::new(255, 255, 255, 255), RGBA8::new(0, 0, 0, 255)];
+        // Cannot fail, since the palette is valid.
+        let img = self.quantize(&palette, dithering).unwrap();
+
+        let width = self.width() as usize;
+        let stride = (width + 7) >> 3;
+        let mut data = vec![0; stride * self.height() as usize];
+        for (y, row) in img.data.chunks(width).enumerate() {
+            for (x, idx) in row.iter().enumerate() {
+                if *idx == 1 {
+                    data[y * stride + x / 8] |= 0x80 >> (x % 8);
+                }
+            }
+        }
+
+        data
+    }
+}
+
+/// Composites a pixel over white.
+fn flatten(c: &RGBA8) -> [f32; 3] {
+    let a = c.a as f32 / 255.0;
+    let blend = |v: u8| v as f32 * a + 255.0 * (1.0 - a);
+    [blend(c.r), blend(c.g), blend(c.b)]
+}
+
+fn nearest_color(palette: &[RGBA8], c: [f32; 3]) -> usize {
+    let mut best_idx = 0;
+    let mut best_dist = f32::MAX;
+    for (i, p) in palette.iter().enumerate() {
+        let dr = c[0] - p.r as f32;
+        let dg = c[1] - p.g as f32;
+        let db = c[2] - p.b as f32;
+        let dist = dr * dr + dg * dg + db * db;
+        if dist < best_dist {
+            best_dist = dist;
+            best_idx = i;
+        }
+    }
+
+    best_idx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gray_image(width: u32, height: u32, v: u8) -> Image {
+        Image {
+            data: [v, v, v, 255].repeat((width * height) as usize),
+            width,
+            height,
+        }
+    }
+
+    fn count_black(data: &[u8]) -> u32 {
+        data.iter().map(|b| b.count_ones()).sum()
+    }
+
+    #[test]
+    fn palette_size() {
+        let img = gray_image(2, 2, 0);
+        assert!(img.quantize(&[], Dithering::None).is_none());
+        assert!(img.quantize(&[RGBA8::new(0, 0, 0, 255); 257], Dithering::None).is_none());
+
+        let palette = [RGBA8::new(0, 0, 0, 255); 256];
+        let indexed = img.quantize(&palette, Dithering::None).unwrap();
+        assert_eq!(indexed.palette.len(), 256);
+        assert_eq!(indexed.data, [0; 4]);
+    }
+
+    #[test]
+    fn nearest_without_dithering() {
+        let palette = [
+            RGBA8::new(255, 0, 0, 255),
+            RGBA8::new(0, 255, 0, 255),
+            RGBA8::new(255, 255, 255, 255),
+        ];
+        let img = Image {
+            data: vec![
+                250, 10, 10, 255,
+                20, 200, 30, 255,
+                0, 0, 0, 0, // transparent pixels are white
+            ],
+            width: 3,
+            height: 1,
+        };
+
+        let indexed = img.quantize(&palette, Dithering::None).unwrap();
+        assert_eq!(indexed.data, [0, 1, 2]);
+    }
+
+    #[test]
+    fn monochrome_threshold() {
+        assert_eq!(gray_image(1, 1, 127).to_monochrome(Dithering::None), [0x80]);
+        assert_eq!(gray_image(1, 1, 128).to_monochrome(Dithering::None), [0x00]);
+    }
+
+    #[test]
+    fn monochrome_packing() {
+        // Rows start at a byte boundary.
+        let data = gray_image(10, 2, 0).to_monochrome(Dithering::None);
+        assert_eq!(data, [0xFF, 0xC0, 0xFF, 0xC0]);
+    }
+
+    #[test]
+    fn monochrome_dithering() {
+        let img = gray_image(16, 16, 128);
+        assert_eq!(count_black(&img.to_monochrome(Dithering::None)), 0);
+
+        // Exactly half of each 4x4 Bayer cell is black.
+        let data = img.to_monochrome(Dithering::Ordered);
+        assert_eq!(count_black(&data), 128);
+        assert_ne!(data[0], 0x00);
+        assert_ne!(data[0], 0xFF);
+
+        let n = count_black(&img.to_monochrome(Dithering::FloydSteinberg));
+        assert!((120..=136).contains(&n), "{}", n);
+    }
+}
@@ -27,6 +27,42 @@ pub(crate) enum RenderState {
 }
 
 
+/// Reports the top-level nodes rendering progress to `RenderOptions::progress`.
+pub(crate) struct Progress {
+    hook: ProgressHook,
+    done: usize,
+    total: usize,
+}
+
+impl Progress {
+    pub(crate) fn new(root: &usvg::Node, opt: &RenderOptions) -> Option<Self> {
+        let hook = opt.progress.clone()?;
+        let total = root.children().map(|node| Self::weight(&node)).sum();
+        Some(Progress { hook, done: 0, total })
+    }
+
+    /// Marks a top-level node as rendered.
+    pub(crate) fn advance(&mut self, node: &usvg::Node) {
+        let weight = Self::weight(node);
+        if weight == 0 {
+            return;
+        }
+
+        self.done += weight;
+        (self.hook)(self.done as f32 / self.total as f32);
+    }
+
+    fn weight(node: &usvg::Node) -> usize {
+        // Defs are not rendered directly.
+        if let usvg::NodeKind::Defs = *node.borrow() {
+            return 0;
+        }
+
+        node.descendants().count()
+    }
+}
+
+
 pub(crate) trait ConvTransform {
     fn to_native(&self) -> skia::Transform;
     fn from_native(_: skia::Transform) -> Self;
@@ -50,14 +86,18 @@ impl ConvTransform for usvg::Transform {
 
 pub(crate) fn render_to_canvas(
     tree: &usvg::Tree,
+    opt: &RenderOptions,
     img_size: ScreenSize,
     canvas: &mut tiny_skia::Canvas,
 ) {
-    render_node_to_canvas(&tree.root(), tree.svg_node().view_box, img_size, &mut RenderState::Ok, canvas);
+    render_node_to_canvas(
+        &tree.root(), opt, tree.svg_node().view_box, img_size, &mut RenderState::Ok, canvas,
+    );
 }
 
 pub(crate) fn render_node_to_canvas(
     node: &usvg::Node,
+    opt: &RenderOptions,
     view_box: usvg::ViewBox,
     img_size: ScreenSize,
     state: &mut RenderState,
@@ -73,7 +113,7 @@ pub(crate) fn render_node_to_canvas(
     ts.append(&node.transform());
 
     canvas.concat(ts.to_native());
-    render_node(node, state, &mut layers, canvas);
+    render_node(node, opt, state, &mut layers, canvas);
     canvas.set_transform(curr_ts);
 }
 
@@ -93,8 +133,206 @@ pub(crate) fn create_root_image(
     Some((img, img_size))
 }
 
+/// Scales the `surface` to the `size` using the nearest-neighbor filtering.
+pub(crate) fn resize_nearest(
+    surface: &skia::Surface,
+    size: ScreenSize,
+) -> Option<skia::Surface> {
+    let mut img = create_subsurface(size)?;
+    img.draw_surface_rect(
+        surface, 0.0, 0.0, size.width() as f32, size.height() as f32, skia::FilterQuality::None,
+    );
+
+    Some(img)
+}
+
+/// Scales the `surface` down to the `size` by averaging each `factor`x`factor` pixels block.
+///
+/// The `surface` must be exactly `factor` times bigger than the `size`.
+pub(crate) fn downsample_box(
+    surface: &skia::Surface,
+    factor: u32,
+    size: ScreenSize,
+) -> Option<skia::Surface> {
+    let mut img = create_subsurface(size)?;
+
+    let factor = factor as usize;
+    let count = (factor * factor) as u32;
+
+    let src = surface.data_u8();
+    let src_stride = src.len() / surface.height() as usize;
+    let height = size.height() as usize;
+    let mut dst = img.data_mut();
+    let dst_stride = dst.len() / height;
+    for y in 0..height {
+        for x in 0..size.width() as usize {
+            let mut sum = [0u32; 4];
+            for sy in y * factor..(y + 1) * factor {
+                let row = sy * src_stride;
+                for sx in x * factor..(x + 1) * factor {
+                    let idx = row + sx * 4;
+                    for c in 0..4 {
+                        sum[c] += src[idx + c] as u32;
+                    }
+                }
+            }
+
+            let idx = y * dst_stride + x * 4;
+            for c in 0..4 {
+                // Rounded, so a fully opaque block will stay opaque.
+                dst[idx + c] = ((sum[c] + count / 2) / count) as u8;
+            }
+        }
+    }
+
+    Some(img)
+}
+
+/// Raises each color channel of the `surface` to the power of `gamma`.
+///
+/// Alpha is not affected. Non-positive values are ignored.
+pub(crate) fn apply_gamma(gamma: f32, surface: &mut skia::Surface) {
+    if !(gamma > 0.0 && gamma.is_finite()) || gamma == 1.0 {
+        return;
+    }
+
+    let mut lut = [0u8; 256];
+    for (i, v) in lut.iter_mut().enumerate() {
+        *v = ((i as f32 / 255.0).powf(gamma) * 255.0).round() as u8;
+    }
+
+    // The surface is premultiplied, so channels have to be demultiplied first.
+    let mut data = surface.data_mut();
+    for p in data.chunks_mut(4) {
+        let a = p[3] as u32;
+        if a == 0 {
+            continue;
+        }
+
+        for c in &mut p[0..3] {
+            let straight = ((*c as u32 * 255 + a / 2) / a).min(255);
+            *c = ((lut[straight as usize] as u32 * a + 127) / 255) as u8;
+        }
+    }
+}
+
+/// Adds `padding` pixels on each side of the `surface`.
+///
+/// The padding is filled with the `background` or left transparent.
+pub(crate) fn add_padding(
+    surface: &skia::Surface,
+    padding: u32,
+    background: Option<usvg::Color>,
+) -> Option<skia::Surface> {
+    let size = ScreenSize::new(
+        surface.width().checked_add(padding.checked_mul(2)?)?,
+        surface.height().checked_add(padding.checked_mul(2)?)?,
+    )?;
+
+    let mut img = create_subsurface(size)?;
+    if let Some(c) = background {
+        img.fill(c.red, c.green, c.blue, 255);
+    }
+
+    img.draw_surface(
+        surface, padding as f32, padding as f32, 255,
+        skia::BlendMode::Source, skia::FilterQuality::None,
+    );
+
+    Some(img)
+}
+
+/// Fills the `canvas` with a checkerboard pattern.
+pub(crate) fn draw_checkerboard(
+    checkerboard: &Checkerboard,
+    img_size: ScreenSize,
+    canvas: &mut skia::Canvas,
+) {
+    let c = checkerboard.color2;
+    canvas.fill(c.red, c.green, c.blue, 255);
+
+    let mut paint = skia::Paint::new();
+    paint.set_style(skia::PaintStyle::Fill);
+    let c = checkerboard.color1;
+    paint.set_color(c.red, c.green, c.blue, 255);
+
+    let size = std::cmp::max(checkerboard.size, 1);
+    for y in (0..img_size.height()).step_by(size as usize) {
+        for x in (0..img_size.width()).step_by(size as usize) {
+            if (x / size + y / size) & 1 == 0 {
+                canvas.draw_rect(x as f32, y as f32, size as f32, size as f32, &paint);
+            }
+        }
+    }
+}
+
+/// Draws bounding boxes of the `node` and all its descendants.
+///
+/// Nodes inside `defs` are ignored.
+pub(crate) fn draw_bboxes(
+    node: &usvg::Node,
+    view_box: usvg::ViewBox,
+    img_size: ScreenSize,
+    canvas: &mut skia::Canvas,
+) {
+    let tree = node.tree();
+
+    let curr_ts = canvas.get_transform();
+    apply_viewbox_transform(view_box, img_size, canvas);
+
+    let mut paint = skia::Paint::new();
+    paint.set_style(skia::PaintStyle::Stroke);
+    paint.set_anti_alias(true);
+    // A zero width stroke is a hairline in Skia,
+    // which is always one pixel wide regardless of the current transform.
+    paint.set_stroke_width(0.0);
+
+    for node in node.descendants() {
+        let (r, g, b) = match *node.borrow() {
+            usvg::NodeKind::Path(_) => (255, 0, 0),
+            usvg::NodeKind::Image(_) => (0, 0, 255),
+            usvg::NodeKind::Group(_) => (0, 160, 0),
+            _ => continue,
+        };
+
+        if tree.is_in_defs(&node) {
+            continue;
+        }
+
+        if let Some(bbox) = node.calculate_bbox() {
+            paint.set_color(r, g, b, 255);
+            canvas.draw_rect(
+                bbox.x() as f32, bbox.y() as f32, bbox.width() as f32, bbox.height() as f32,
+                &paint,
+            );
+        }
+    }
+
+    canvas.set_transform(curr_ts);
+}
+
+/// Flips the painter vertically, so the content is drawn upside down.
+pub(crate) fn apply_flip_transform(img_size: ScreenSize, canvas: &mut skia::Canvas) {
+    canvas.concat(flip_transform(img_size).to_native());
+}
+
+fn flip_transform(img_size: ScreenSize) -> usvg::Transform {
+    usvg::Transform::new(1.0, 0.0, 0.0, -1.0, 0.0, img_size.height() as f64)
+}
+
+/// Flips the `surface` rows in place.
+fn flip_rows(surface: &mut skia::Surface) {
+    let height = surface.height() as usize;
+    let mut data = surface.data_mut();
+    let stride = data.len() / height;
+    for y in 0..height / 2 {
+        let (top, bottom) = data.split_at_mut((height - y - 1) * stride);
+        top[y * stride..(y + 1) * stride].swap_with_slice(&mut bottom[..stride]);
+    }
+}
+
 /// Applies viewbox transformation to the painter.
-fn apply_viewbox_transform(
+pub(crate) fn apply_viewbox_transform(
     view_box: usvg::ViewBox,
     img_size: ScreenSize,
     canvas: &mut skia::Canvas,
@@ -105,22 +343,23 @@ fn apply_viewbox_transform(
 
 pub(crate) fn render_node(
     node: &usvg::Node,
+    opt: &RenderOptions,
     state: &mut RenderState,
     layers: &mut Layers,
     canvas: &mut skia::Canvas,
 ) -> Option<Rect> {
     match *node.borrow() {
         usvg::NodeKind::Svg(_) => {
-            render_group(node, state, layers, canvas)
+            render_group(node, opt, state, layers, canvas)
         }
         usvg::NodeKind::Path(ref path) => {
-            crate::path::draw(&node.tree(), path, skia::BlendMode::SourceOver, canvas)
+            crate::path::draw(&node.tree(), Some(node), path, opt, skia::BlendMode::SourceOver, canvas)
         }
         usvg::NodeKind::Image(ref img) => {
-            Some(crate::image::draw(img, canvas))
+            Some(crate::image::draw(img, opt, canvas))
         }
         usvg::NodeKind::Group(ref g) => {
-            render_group_impl(node, g, state, layers, canvas)
+            render_group_impl(node, g, opt, state, layers, canvas)
         }
         _ => None,
     }
@@ -128,6 +367,7 @@ pub(crate) fn render_node(
 
 pub(crate) fn render_group(
     parent: &usvg::Node,
+    opt: &RenderOptions,
     state: &mut RenderState,
     layers: &mut Layers,
     canvas: &mut skia::Canvas,
@@ -135,6 +375,13 @@ pub(crate) fn render_group(
     let curr_ts = canvas.get_transform();
     let mut g_bbox = Rect::new_bbox();
 
+    // Only the main root rendering is reported and not the filters background one.
+    let mut progress = if parent.parent().is_none() && *state == RenderState::Ok {
+        Progress::new(parent, opt)
+    } else {
+        None
+    };
+
     for node in parent.children() {
         match state {
             RenderState::Ok => {}
@@ -150,7 +397,7 @@ pub(crate) fn render_group(
 
         canvas.concat(node.transform().to_native());
 
-        let bbox = render_node(&node, state, layers, canvas);
+        let bbox = render_node(&node, opt, state, layers, canvas);
         if let Some(bbox) = bbox {
             if let Some(bbox) = bbox.transform(&node.transform()) {
                 g_bbox = g_bbox.expand(bbox);
@@ -159,6 +406,10 @@ pub(crate) fn render_group(
 
         // Revert transform.
         canvas.set_transform(curr_ts);
+
+        if let Some(ref mut progress) = progress {
+            progress.advance(&node);
+        }
     }
 
     // Check that bbox was changed, otherwise we will have a rect with x/y set to f64::MAX.
@@ -172,6 +423,7 @@ pub(crate) fn render_group(
 fn render_group_impl(
     node: &usvg::Node,
     g: &usvg::Group,
+    opt: &RenderOptions,
     state: &mut RenderState,
     layers: &mut Layers,
     canvas: &mut skia::Canvas,
@@ -181,9 +433,22 @@ fn render_group_impl(
 
     let curr_ts = canvas.get_transform();
 
+    // Filters support only scaling and translation, so with `RenderOptions::flip_y`
+    // a filtered group is rendered and filtered unflipped and flipped afterwards.
+    let flipped = opt.flip_y && g.filter.is_some();
+    let unflipped_opt;
+    let (opt, content_ts) = if flipped {
+        unflipped_opt = RenderOptions { flip_y: false, ..opt.clone() };
+        let mut ts = flip_transform(layers.image_size());
+        ts.append(&usvg::Transform::from_native(curr_ts));
+        (&unflipped_opt, ts.to_native())
+    } else {
+        (opt, curr_ts)
+    };
+
     let bbox = {
-        sub_surface.set_transform(curr_ts);
-        render_group(node, state, layers, &mut sub_surface)
+        sub_surface.set_transform(content_ts);
+        render_group(node, opt, state, layers, &mut sub_surface)
     };
 
     // During the background rendering for filters,
@@ -208,13 +473,20 @@ fn render_group_impl(
     if let Some(ref id) = g.filter {
         if let Some(filter_node) = node.tree().defs_by_id(id) {
             if let usvg::NodeKind::Filter(ref filter) = *filter_node.borrow() {
-                let ts = usvg::Transform::from_native(curr_ts);
-                let background = prepare_filter_background(node, filter, layers.image_size());
-                let fill_paint = prepare_filter_fill_paint(node, filter, bbox, ts, &sub_surface);
-                let stroke_paint = prepare_filter_stroke_paint(node, filter, bbox, ts, &sub_surface);
-                crate::filter::apply(filter, bbox, &ts, &node.tree(),
-                                     background.as_ref(), fill_paint.as_ref(), stroke_paint.as_ref(),
-                                     &mut sub_surface);
+                let ts = usvg::Transform::from_native(content_ts);
+                let background = prepare_filter_background(node, filter, opt, layers.image_size());
+                let fill_paint = prepare_filter_fill_paint(node, filter, opt, bbox, ts, &sub_surface);
+                let stroke_paint = prepare_filter_stroke_paint(node, filter, opt, bbox, ts, &sub_surface);
+                let extra_inputs = crate::filter::FilterExtraInputs {
+                    background: background.as_ref(),
+                    fill_paint: fill_paint.as_ref(),
+                    stroke_paint: stroke_paint.as_ref(),
+                };
+                crate::filter::apply(filter, bbox, &ts, &node.tree(), opt, extra_inputs, &mut sub_surface);
+
+                if flipped {
+                    flip_rows(&mut sub_surface);
+                }
             }
         }
     }
@@ -225,7 +497,7 @@ fn render_group_impl(
             if let Some(clip_node) = node.tree().defs_by_id(id) {
                 if let usvg::NodeKind::ClipPath(ref cp) = *clip_node.borrow() {
                     sub_surface.set_transform(curr_ts);
-                    crate::clip::clip(&clip_node, cp, bbox, layers, &mut sub_surface);
+                    crate::clip::clip(&clip_node, cp, opt, bbox, layers, &mut sub_surface);
                 }
             }
         }
@@ -234,7 +506,7 @@ fn render_group_impl(
             if let Some(mask_node) = node.tree().defs_by_id(id) {
                 if let usvg::NodeKind::Mask(ref mask) = *mask_node.borrow() {
                     sub_surface.set_transform(curr_ts);
-                    crate::mask::mask(&mask_node, mask, bbox, layers, &mut sub_surface);
+                    crate::mask::mask(&mask_node, mask, opt, bbox, layers, &mut sub_surface);
                 }
             }
         }
@@ -260,6 +532,7 @@ fn render_group_impl(
 fn prepare_filter_background(
     parent: &usvg::Node,
     filter: &usvg::Filter,
+    opt: &RenderOptions,
     img_size: ScreenSize,
 ) -> Option<skia::Surface> {
     let start_node = parent.filter_background_start_node(filter)?;
@@ -270,7 +543,7 @@ fn prepare_filter_background(
 
     // Render from the `start_node` until the `parent`. The `parent` itself is excluded.
     let mut state = RenderState::RenderUntil(parent.clone());
-    crate::render::render_node_to_canvas(&start_node, view_box, img_size, &mut state, &mut img);
+    crate::render::render_node_to_canvas(&start_node, opt, view_box, img_size, &mut state, &mut img);
 
     Some(img)
 }
@@ -285,6 +558,7 @@ fn prepare_filter_background(
 fn prepare_filter_fill_paint(
     parent: &usvg::Node,
     filter: &usvg::Filter,
+    opt: &RenderOptions,
     bbox: Option<Rect>,
     ts: usvg::Transform,
     canvas: &skia::Surface,
@@ -295,8 +569,9 @@ fn prepare_filter_fill_paint(
         if let Some(paint) = g.filter_fill.clone() {
             let style_bbox = bbox.unwrap_or_else(|| Rect::new(0.0, 0.0, 1.0, 1.0).unwrap());
             let fill = Some(usvg::Fill::from_paint(paint));
-            let fill = crate::paint_server::fill(&parent.tree(), &fill, style_bbox, ts);
-            surface.draw_rect(0.0, 0.0, region.width() as f32, region.height() as f32, &fill);
+            if let Some(fill) = crate::paint_server::fill(&parent.tree(), &fill, opt, style_bbox, ts) {
+                surface.draw_rect(0.0, 0.0, region.width() as f32, region.height() as f32, &fill);
+            }
         }
     }
 
@@ -307,6 +582,7 @@ fn prepare_filter_fill_paint(
 fn prepare_filter_stroke_paint(
     parent: &usvg::Node,
     filter: &usvg::Filter,
+    opt: &RenderOptions,
     bbox: Option<Rect>,
     ts: usvg::Transform,
     canvas: &skia::Surface,
@@ -317,8 +593,9 @@ fn prepare_filter_stroke_paint(
         if let Some(paint) = g.filter_stroke.clone() {
             let style_bbox = bbox.unwrap_or_else(|| Rect::new(0.0, 0.0, 1.0, 1.0).unwrap());
             let fill = Some(usvg::Fill::from_paint(paint));
-            let fill = crate::paint_server::fill(&parent.tree(), &fill, style_bbox, ts);
-            surface.draw_rect(0.0, 0.0, region.width() as f32, region.height() as f32, &fill);
+            if let Some(fill) = crate::paint_server::fill(&parent.tree(), &fill, opt, style_bbox, ts) {
+                surface.draw_rect(0.0, 0.0, region.width() as f32, region.height() as f32, &fill);
+            }
         }
     }
 
@@ -0,0 +1,237 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{RenderOptions, ScreenSize};
+
+/// A single-channel signed distance field.
+///
+/// Each byte stores a distance to the nearest shape edge. Values from `128` and above
+/// are inside the shape and values below are outside, with the edge at `127.5`.
+/// `0` and `255` correspond to the `spread` distance or further.
+#[derive(Clone)]
+pub struct DistanceField {
+    data: Vec<u8>,
+    width: u32,
+    height: u32,
+}
+
+impl DistanceField {
+    /// Returns the field width.
+    ///
+    /// Never zero.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the field height.
+    ///
+    /// Never zero.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the field size.
+    pub fn size(&self) -> ScreenSize {
+        ScreenSize::new(self.width, self.height).unwrap()
+    }
+
+    /// Returns the field content as `u8` slice.
+    ///
+    /// One byte per pixel, rows are tightly packed.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the field content as `Vec<u8>`.
+    pub fn take(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+impl std::fmt::Debug for DistanceField {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("DistanceField")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .finish()
+    }
+}
+
+/// Renders an SVG to a signed distance field using the provided rendering options.
+///
+/// The field is computed from the combined coverage of all elements,
+/// where pixels with at least 50% alpha are inside the shape.
+/// `spread` is the maximum encoded distance in pixels.
+///
+/// `RenderOptions::padding` can be used to reserve space for the field around the shape.
+///
+/// Returns `None` when the image cannot be rendered or `spread` is not positive.
+pub fn render_sdf(
+    tree: &usvg::Tree,
+    fit_to: usvg::FitTo,
+    spread: f32,
+    opt: &RenderOptions,
+) -> Option<DistanceField> {
+    if !(spread > 0.0 && spread.is_finite()) {
+        return None;
+    }
+
+    let (view_box, size) = crate::root_view_box(tree, opt);
+    let img = crate::render_root(&tree.root(), view_box, size, fit_to, None, opt)?;
+
+    let width = img.width() as usize;
+    let height = img.height() as usize;
+    let inside: Vec<bool> = img.data_u8().chunks(4).map(|p| p[3] >= 128).collect();
+
+    let dist_to_outside = distance_transform(&inside, width, height, false);
+    let dist_to_inside = distance_transform(&inside, width, height, true);
+
+    let mut data = Vec::with_capacity(width * height);
+    for (i, is_inside) in inside.iter().enumerate() {
+        // Distances are measured between pixel centers, so the edge is half a pixel away.
+        let d = if *is_inside {
+            dist_to_outside[i].sqrt() - 0.5
+        } else {
+            0.5 - dist_to_inside[i].sqrt()
+        };
+
+        let v = 0.5 + d / (2.0 * spread as f64);
+        data.push((v.clamp(0.0, 1.0) * 255.0).round() as u8);
+    }
+
+    Some(DistanceField {
+        data,
+        width: img.width(),
+        height: img.height(),
+    })
+}
+
+/// Computes a squared Euclidean distance from each pixel to the nearest pixel
+/// whose `mask` value equals `target`.
+fn distance_transform(mask: &[bool], width: usize, height: usize, target: bool) -> Vec<f64> {
+    const INF: f64 = 1e20;
+
+    let mut grid: Vec<f64> = mask.iter().map(|m| if *m == target { 0.0 } else { INF }).collect();
+
+    let len = width.max(height);
+    let mut f = vec![0.0; len];
+    let mut d = vec![0.0; len];
+    let mut v = vec![0; len];
+    let mut z = vec![0.0; len + 1];
+
+    for x in 0..width {
+        for y in 0..height {
+            f[y] = grid[y * width + x];
+        }
+
+        distance_transform_1d(&f[..height], &mut d, &mut v, &mut z);
+
+        for y in 0..height {
+            grid[y * width + x] = d[y];
+        }
+    }
+
+    for y in 0..height {
+        let row = &mut grid[y * width..(y + 1) * width];
+        f[..width].copy_from_slice(row);
+        distance_transform_1d(&f[..width], &mut d, &mut v, &mut z);
+        row.copy_from_slice(&d[..width]);
+    }
+
+    grid
+}
+
+/// A one-dimensional squared distance transform.
+///
+/// Based on "Distance Transforms of Sampled Functions" by Felzenszwalb and Huttenlocher.
+fn distance_transform_1d(f: &[f64], d: &mut [f64], v: &mut [usize], z: &mut [f64]) {
+    let n = f.len();
+    let intersection = |q: usize, p: usize| {
+        ((f[q] + (q * q) as f64) - (f[p] + (p * p) as f64)) / (2.0 * q as f64 - 2.0 * p as f64)
+    };
+
+    let mut k = 0;
+    v[0] = 0;
+    z[0] = f64::NEG_INFINITY;
+    z[1] = f64::INFINITY;
+
+    for q in 1..n {
+        let mut s = intersection(q, v[k]);
+        while s <= z[k] {
+            k -= 1;
+            s = intersection(q, v[k]);
+        }
+
+        k += 1;
+        v[k] = q;
+        z[k] = s;
+        z[k + 1] = f64::INFINITY;
+    }
+
+    k = 0;
+    for (q, d) in d.iter_mut().enumerate().take(n) {
+        while z[k + 1] < q as f64 {
+            k += 1;
+        }
+
+        let dq = q as f64 - v[k] as f64;
+        *d = dq * dq + f[v[k]];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distance_transform_row() {
+        let mask = [true, false, false, false, false];
+        assert_eq!(distance_transform(&mask, 5, 1, true), [0.0, 1.0, 4.0, 9.0, 16.0]);
+        assert_eq!(distance_transform(&mask, 5, 1, false), [1.0, 0.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn distance_transform_grid() {
+        let mut mask = [false; 25];
+        mask[2 * 5 + 2] = true;
+
+        let grid = distance_transform(&mask, 5, 5, true);
+        for y in 0..5 {
+            for x in 0..5 {
+                let (dx, dy) = (x as f64 - 2.0, y as f64 - 2.0);
+                assert_eq!(grid[y * 5 + x], dx * dx + dy * dy);
+            }
+        }
+    }
+
+    #[test]
+    fn render() {
+        let svg = "
+<svg viewBox='0 0 30 30' xmlns='http://www.w3.org/2000/svg'>
+    <rect x='10' y='0' width='10' height='30'/>
+</svg>
+";
+
+        let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+        let opt = RenderOptions::default();
+        assert!(render_sdf(&tree, usvg::FitTo::Original, 0.0, &opt).is_none());
+
+        let field = render_sdf(&tree, usvg::FitTo::Original, 4.0, &opt).unwrap();
+        assert_eq!((field.width(), field.height()), (30, 30));
+
+        // The edge is half a pixel away from the nearest pixel centers on both sides.
+        let row = &field.data()[15 * 30..16 * 30];
+        assert_eq!(row[9], 112);
+        assert_eq!(row[10], 143);
+        assert_eq!(row[19], 143);
+        assert_eq!(row[20], 112);
+
+        // 2.5 pixels inside.
+        assert_eq!(row[12], ((0.5 + 2.5 / 8.0) * 255.0_f64).round() as u8);
+
+        // Beyond the spread.
+        assert_eq!(row[0], 0);
+        assert_eq!(row[15], 255);
+    }
+}
@@ -0,0 +1,114 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use usvg::{NodeExt, ScreenSize};
+
+use crate::{Image, RenderOptions};
+use crate::layers::Layers;
+use crate::render::{self, ConvTransform, Progress, RenderState};
+
+/// A resumable SVG rendering task.
+///
+/// Renders an SVG one top-level node at a time, so the caller can pause
+/// between the steps. Useful in environments where a long synchronous
+/// rendering would block the main thread, like a browser.
+///
+/// # Example
+///
+/// ```no_run
+/// # let tree = usvg::Tree::from_str("<svg/>", &usvg::Options::default()).unwrap();
+/// let mut task = resvg::RenderTask::new(
+///     &tree, usvg::FitTo::Original, None, resvg::RenderOptions::default(),
+/// ).unwrap();
+///
+/// while !task.step(10) {
+///     // Yield to the host.
+/// }
+///
+/// let img = task.finish().unwrap();
+/// ```
+#[allow(missing_debug_implementations)]
+pub struct RenderTask {
+    root: usvg::Node,
+    nodes: Vec<usvg::Node>,
+    next: usize,
+    view_box: usvg::ViewBox,
+    size: ScreenSize,
+    fit_to: usvg::FitTo,
+    background: Option<usvg::Color>,
+    opt: RenderOptions,
+    img_size: ScreenSize,
+    img: tiny_skia::Surface,
+    layers: Layers,
+    progress: Option<Progress>,
+}
+
+impl RenderTask {
+    /// Creates a new rendering task.
+    ///
+    /// Returns `None` when the image cannot be allocated.
+    pub fn new(
+        tree: &usvg::Tree,
+        fit_to: usvg::FitTo,
+        background: Option<usvg::Color>,
+        opt: RenderOptions,
+    ) -> Option<Self> {
+        let (view_box, size) = crate::root_view_box(tree, &opt);
+        let (img, img_size) = crate::create_root_image(size, fit_to, background, &opt)?;
+        let progress = Progress::new(&tree.root(), &opt);
+
+        Some(RenderTask {
+            root: tree.root(),
+            nodes: tree.root().children().collect(),
+            next: 0,
+            view_box,
+            size,
+            fit_to,
+            background,
+            opt,
+            img_size,
+            img,
+            layers: Layers::new(img_size),
+            progress,
+        })
+    }
+
+    /// Renders up to `count` next top-level nodes.
+    ///
+    /// Returns `true` when all nodes were rendered.
+    pub fn step(&mut self, count: usize) -> bool {
+        let end = std::cmp::min(self.next.saturating_add(count), self.nodes.len());
+        for node in &self.nodes[self.next..end] {
+            let curr_ts = self.img.get_transform();
+            render::apply_viewbox_transform(self.view_box, self.img_size, &mut self.img);
+            self.img.concat(node.transform().to_native());
+            render::render_node(node, &self.opt, &mut RenderState::Ok, &mut self.layers, &mut self.img);
+            self.img.set_transform(curr_ts);
+
+            if let Some(ref mut progress) = self.progress {
+                progress.advance(node);
+            }
+        }
+
+        self.next = end;
+        self.is_finished()
+    }
+
+    /// Checks that all nodes were rendered.
+    pub fn is_finished(&self) -> bool {
+        self.next == self.nodes.len()
+    }
+
+    /// Renders the remaining nodes and returns the image.
+    pub fn finish(mut self) -> Option<Image> {
+        let count = self.nodes.len() - self.next;
+        self.step(count);
+
+        let img = crate::finish_root_image(
+            &self.root, self.view_box, self.size, self.fit_to, self.img, self.background, &self.opt,
+        )?;
+
+        Some(Image::from_surface(img))
+    }
+}
@@ -2,7 +2,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use crate::{ImageRef, ImageRefMut};
+use crate::{ImageRef, ImageRefMut, RGBA8};
 
 /// A color channel.
 #[allow(missing_docs)]
@@ -16,6 +16,8 @@ pub enum ColorChannel { R, G, B, A }
 ///
 /// `sx` and `sy` indicate canvas scale.
 ///
+/// `src` is sampled using the bilinear interpolation.
+///
 /// # Panics
 ///
 /// When `src`, `map` and `dest` have different sizes.
@@ -50,17 +52,12 @@ pub fn displacement_map(
 
         let dx = calc_offset(x_channel_selector);
         let dy = calc_offset(y_channel_selector);
-        let ox = (x as f64 + dx * sx).round() as i32;
-        let oy = (y as f64 + dy * sy).round() as i32;
-
-        // TODO: we should use some kind of anti-aliasing when offset is on a pixel border
+        let ox = x as f64 + dx * sx;
+        let oy = y as f64 + dy * sy;
 
-        if    x < w as u32 && y < h as u32
-           && ox >= 0 && ox < w && oy >= 0 && oy < h
-        {
-            let idx = (oy * w + ox) as usize;
-            let idx1 = (y * w as u32 + x) as usize;
-            dest.data[idx1] = src.data[idx];
+        if x < w as u32 && y < h as u32 {
+            let idx = (y * w as u32 + x) as usize;
+            dest.data[idx] = sample_bilinear(&src, ox, oy);
         }
 
         x += 1;
@@ -70,3 +67,41 @@ pub fn displacement_map(
         }
     }
 }
+
+/// Samples `src` at a fractional position using the bilinear interpolation.
+///
+/// Pixels outside the image are treated as transparent black.
+fn sample_bilinear(src: &ImageRef, x: f64, y: f64) -> RGBA8 {
+    let x0 = x.floor();
+    let y0 = y.floor();
+    let tx = x - x0;
+    let ty = y - y0;
+    let x0 = x0 as i32;
+    let y0 = y0 as i32;
+
+    let pixel_at = |x: i32, y: i32| {
+        if x >= 0 && x < src.width as i32 && y >= 0 && y < src.height as i32 {
+            src.data[(y * src.width as i32 + x) as usize]
+        } else {
+            RGBA8::default()
+        }
+    };
+
+    let p00 = pixel_at(x0, y0);
+    let p10 = pixel_at(x0 + 1, y0);
+    let p01 = pixel_at(x0, y0 + 1);
+    let p11 = pixel_at(x0 + 1, y0 + 1);
+
+    let lerp = |c00: u8, c10: u8, c01: u8, c11: u8| {
+        let top = c00 as f64 * (1.0 - tx) + c10 as f64 * tx;
+        let bottom = c01 as f64 * (1.0 - tx) + c11 as f64 * tx;
+        (top * (1.0 - ty) + bottom * ty).round() as u8
+    };
+
+    RGBA8 {
+        r: lerp(p00.r, p10.r, p01.r, p11.r),
+        g: lerp(p00.g, p10.g, p01.g, p11.g),
+        b: lerp(p00.b, p10.b, p01.b, p11.b),
+        a: lerp(p00.a, p10.a, p01.a, p11.a),
+    }
+}
@@ -318,3 +318,25 @@ impl FuzzyZero for f64 {
         self.fuzzy_eq(&0.0)
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn premultiplied_round_trip() {
+        // Every valid premultiplied pixel must survive
+        // the demultiply/multiply round trip within 1 LSB.
+        for a in 0..=255u8 {
+            for c in 0..=a {
+                let mut data = [RGBA8::new(c, c, c, a)];
+                demultiply_alpha(&mut data);
+                multiply_alpha(&mut data);
+                let p = data[0];
+                assert!((p.r as i16 - c as i16).abs() <= 1, "{} {} -> {}", c, a, p.r);
+                assert_eq!(p.a, a);
+            }
+        }
+    }
+}
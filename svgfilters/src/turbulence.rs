@@ -99,7 +99,7 @@ fn init(mut seed: i32) -> (Vec<usize>, Vec<Vec<Vec<f64>>>) {
     let mut gradient = vec![vec![vec![0.0; 2]; B_LEN]; 4];
 
     if seed <= 0 {
-        seed = -seed % (RAND_M - 1) + 1;
+        seed = -(seed % (RAND_M - 1)) + 1;
     }
 
     if seed > RAND_M - 1 {
@@ -306,3 +306,17 @@ fn s_curve(t: f64) -> f64 {
 fn lerp(t: f64, a: f64, b: f64) -> f64 {
     a + t * (b - a)
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn init_min_seed() {
+        // Must not overflow while negating the seed.
+        let (lattice_selector, gradient) = init(i32::MIN);
+        assert_eq!(lattice_selector.len(), B_LEN);
+        assert!(gradient.iter().flatten().flatten().all(|n| n.is_finite()));
+    }
+}
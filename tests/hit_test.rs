@@ -0,0 +1,44 @@
+use usvg::NodeExt;
+
+const SVG: &str = "
+<svg viewBox='0 0 200 200' xmlns='http://www.w3.org/2000/svg'>
+    <rect id='default' x='10' y='10' width='80' height='80' fill='none' stroke='black' stroke-width='10'/>
+    <rect id='fill' x='110' y='10' width='80' height='80' fill='none' stroke='black'
+          stroke-width='10' pointer-events='fill'/>
+    <rect id='stroke' x='10' y='110' width='80' height='80' fill='green' stroke='black'
+          stroke-width='10' pointer-events='stroke'/>
+    <circle id='bbox' cx='150' cy='150' r='40' fill='green' pointer-events='bounding-box'/>
+    <rect id='none' x='0' y='0' width='200' height='200' fill='green' fill-opacity='0.1'
+          pointer-events='none'/>
+    <rect id='hidden' x='0' y='0' width='20' height='20' visibility='hidden' pointer-events='all'/>
+</svg>
+";
+
+fn hit(x: f64, y: f64) -> Option<String> {
+    let tree = usvg::Tree::from_str(SVG, &usvg::Options::default()).unwrap();
+    let node = resvg::hit_test(&tree, usvg::FitTo::Original, x, y)?;
+    let id = node.id().to_string();
+    Some(id)
+}
+
+#[test]
+fn pointer_events() {
+    // `visiblePainted` ignores an unpainted fill.
+    assert_eq!(hit(10.0, 50.0).as_deref(), Some("default"));
+    assert_eq!(hit(50.0, 50.0), None);
+
+    // `fill` ignores a stroke, but not an unpainted fill.
+    assert_eq!(hit(150.0, 50.0).as_deref(), Some("fill"));
+    assert_eq!(hit(107.0, 50.0), None);
+
+    // `stroke` ignores a fill.
+    assert_eq!(hit(10.0, 150.0).as_deref(), Some("stroke"));
+    assert_eq!(hit(50.0, 150.0), None);
+
+    // `bounding-box` hits outside of the circle.
+    assert_eq!(hit(113.0, 113.0).as_deref(), Some("bbox"));
+    assert_eq!(hit(150.0, 150.0).as_deref(), Some("bbox"));
+
+    // `all` ignores visibility.
+    assert_eq!(hit(2.0, 2.0).as_deref(), Some("hidden"));
+}
@@ -0,0 +1,37 @@
+use rgb::FromSlice;
+
+const SVG: &str = "
+<svg viewBox='0 0 200 200' xmlns='http://www.w3.org/2000/svg'>
+    <linearGradient id='lg1'>
+        <stop offset='0' stop-color='red' stop-opacity='0'/>
+        <stop offset='0.5' stop-color='green' stop-opacity='0.5'/>
+        <stop offset='1' stop-color='blue'/>
+    </linearGradient>
+    <rect x='20' y='20' width='160' height='160' fill='url(#lg1)'/>
+    <circle cx='100' cy='100' r='60' fill='yellow' opacity='0.3'/>
+</svg>
+";
+
+#[test]
+fn premultiplied_parity() {
+    let tree = usvg::Tree::from_str(SVG, &usvg::Options::default()).unwrap();
+    let opt = resvg::RenderOptions::default();
+    let fit_to = usvg::FitTo::Original;
+
+    let img = resvg::render_with_options(&tree, fit_to, None, &opt).unwrap();
+    let premultiplied = resvg::render_premultiplied(&tree, fit_to, None, &opt).unwrap();
+    assert_eq!(img.width(), premultiplied.width());
+    assert_eq!(img.height(), premultiplied.height());
+
+    let mut data = img.data().to_vec();
+    svgfilters::multiply_alpha(data.as_rgba_mut());
+
+    let stride = premultiplied.stride();
+    let row_len = img.width() as usize * 4;
+    for (y, row) in data.chunks(row_len).enumerate() {
+        let expected = &premultiplied.data()[y * stride..y * stride + row_len];
+        for (a, b) in row.iter().zip(expected) {
+            assert!((*a as i16 - *b as i16).abs() <= 1);
+        }
+    }
+}
@@ -123,8 +123,44 @@ impl std::fmt::Display for Error {
     }
 }
 
+/// Returns rendering options for a test.
+///
+/// `o` tests are rendered using the option from their name,
+/// all other tests are rendered using the default options.
+fn render_options(file_name: &str) -> resvg::RenderOptions {
+    let name = file_name.trim_end_matches(|c: char| c.is_ascii_digit() || c == '-');
+    let builder = resvg::RenderOptions::builder();
+    let builder = match name {
+        "o-checkerboard" => builder.checkerboard(resvg::Checkerboard::default()),
+        "o-clip" => {
+            let mut path = usvg::PathData::new();
+            path.push_move_to(150.0, 20.0);
+            path.push_line_to(280.0, 280.0);
+            path.push_line_to(20.0, 280.0);
+            path.push_close_path();
+            builder.clip(path)
+        }
+        "o-dash-gap-opacity" => builder.dash_gap_opacity(usvg::Opacity::new(0.3)),
+        "o-draw-bboxes" => builder.draw_bboxes(true),
+        "o-gradient-bands" => builder.gradient_bands(5),
+        "o-ignore-root-overflow" => builder.ignore_root_overflow(true),
+        "o-max-gradient-stops" => builder.max_gradient_stops(3),
+        "o-min-path-size" => builder.min_path_size(10.0),
+        "o-output-gamma" => builder.output_gamma(2.2),
+        "o-padding" => builder.padding(10),
+        "o-pixelated" => builder.pixelated(true),
+        "o-strokes-only" => builder.strokes_only(true),
+        "o-supersample" => builder.supersample(4),
+        "o-view-box" => builder.view_box(usvg::Rect::new(0.0, 0.0, 100.0, 100.0).unwrap()),
+        "o-wireframe" => builder.wireframe(usvg::Color::new(255, 0, 0)),
+        _ => builder,
+    };
+
+    builder.build()
+}
+
 fn process(
-    #[allow(unused_variables)] file_name: &str,
+    file_name: &str,
     svg_path: &Path,
     png_path: &Path,
     opt: &usvg::Options,
@@ -132,7 +168,8 @@ fn process(
     let rtree = std::panic::catch_unwind(|| usvg::Tree::from_file(&svg_path, &opt))
         .map_err(|e| ErrorKind::ParsingPanicked(format!("{:?}", e)))??;
 
-    let img = resvg::render(&rtree, usvg::FitTo::Width(IMAGE_SIZE), None)
+    let ropt = render_options(file_name);
+    let img = resvg::render_with_options(&rtree, usvg::FitTo::Width(IMAGE_SIZE), None, &ropt)
         .ok_or_else(|| ErrorKind::RenderingFailed)?;
 
     let expected_data = load_png(png_path);
@@ -239,3 +276,709 @@ fn is_pix_diff(c1: rgb::RGBA8, c2: rgb::RGBA8) -> bool {
     (c1.b as i32 - c2.b as i32).abs() > 1 ||
     (c1.a as i32 - c2.a as i32).abs() > 1
 }
+
+/// Checks that a point is inside of flattened path data.
+fn contains(data: &usvg::PathData, rule: usvg::FillRule, x: f64, y: f64) -> bool {
+    let mut winding = 0;
+    let mut start = (0.0, 0.0);
+    let mut prev = (0.0, 0.0);
+    let mut add_edge = |p0: (f64, f64), p1: (f64, f64)| {
+        if (p0.1 <= y) != (p1.1 <= y) {
+            let t = (y - p0.1) / (p1.1 - p0.1);
+            if p0.0 + t * (p1.0 - p0.0) > x {
+                winding += if p1.1 > p0.1 { 1 } else { -1 };
+            }
+        }
+    };
+
+    for seg in data.flatten(0.1).iter() {
+        match *seg {
+            usvg::PathSegment::MoveTo { x, y } => {
+                add_edge(prev, start);
+                start = (x, y);
+                prev = start;
+            }
+            usvg::PathSegment::LineTo { x, y } => {
+                add_edge(prev, (x, y));
+                prev = (x, y);
+            }
+            usvg::PathSegment::ClosePath => {
+                add_edge(prev, start);
+                prev = start;
+            }
+            usvg::PathSegment::CurveTo { .. } => unreachable!(),
+        }
+    }
+    add_edge(prev, start);
+
+    match rule {
+        usvg::FillRule::NonZero => winding != 0,
+        usvg::FillRule::EvenOdd => winding % 2 != 0,
+    }
+}
+
+#[test]
+fn tree_to_paths() {
+    let svg = "
+<svg viewBox='0 0 100 100' width='200' height='200' xmlns='http://www.w3.org/2000/svg'>
+    <path id='ring' d='M 10 10 H 40 V 40 H 10 Z M 20 20 H 30 V 30 H 20 Z' fill-rule='evenodd'/>
+    <path id='hole' d='M 60 10 H 90 V 40 H 60 Z M 70 20 V 30 H 80 V 20 Z'/>
+    <rect id='stroke' x='20' y='60' width='60' height='30' fill='none' stroke='black' stroke-width='4'/>
+</svg>
+";
+
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    let hit = |paths: &[resvg::FilledPath], x, y| {
+        paths.iter().any(|p| contains(&p.data, p.fill_rule, x, y))
+    };
+
+    let paths = resvg::tree_to_paths(&tree, false);
+    assert_eq!(paths.len(), 2);
+    assert_eq!(paths[0].id, "ring");
+    assert_eq!(paths[0].fill_rule, usvg::FillRule::EvenOdd);
+
+    // Coordinates are in the root user space, not in pixels.
+    assert!(hit(&paths, 15.0, 15.0));
+    assert!(!hit(&paths, 25.0, 25.0));
+    assert!(hit(&paths, 65.0, 15.0));
+    assert!(!hit(&paths, 75.0, 25.0));
+    assert!(!hit(&paths, 20.0, 60.0));
+
+    let paths = resvg::tree_to_paths(&tree, true);
+    assert_eq!(paths.len(), 3);
+    assert_eq!(paths[2].id, "stroke");
+    assert!(hit(&paths, 20.0, 60.0));
+    assert!(hit(&paths, 21.5, 75.0));
+    assert!(hit(&paths, 81.5, 91.5));
+    assert!(!hit(&paths, 50.0, 75.0));
+    assert!(!hit(&paths, 17.0, 75.0));
+}
+
+#[test]
+fn render_layers() {
+    let svg = "
+<svg viewBox='0 0 200 200' xmlns='http://www.w3.org/2000/svg'>
+    <linearGradient id='lg1'>
+        <stop offset='0' stop-color='white'/>
+        <stop offset='1' stop-color='green'/>
+    </linearGradient>
+    <g id='g1'>
+        <rect x='20' y='20' width='100' height='100' fill='url(#lg1)'/>
+    </g>
+    <g id='g2' opacity='0.5'>
+        <circle cx='120' cy='120' r='60' fill='url(#lg1)'/>
+    </g>
+</svg>
+";
+
+    let uopt = usvg::Options { keep_named_groups: true, ..usvg::Options::default() };
+    let tree = usvg::Tree::from_str(svg, &uopt).unwrap();
+    let opt = resvg::RenderOptions::default();
+
+    let layers = resvg::render_layers(&tree, None, usvg::FitTo::Original, None, &opt);
+    let ids: Vec<_> = layers.iter().map(|(id, _)| id.as_str()).collect();
+    assert_eq!(ids, ["g1", "g2"]);
+
+    // Composite layers using source-over.
+    let mut composite = vec![0.0f64; 200 * 200 * 4];
+    for (_, img) in &layers {
+        for (dst, src) in composite.chunks_mut(4).zip(img.data().as_rgba()) {
+            let sa = src.a as f64 / 255.0;
+            let da = dst[3];
+            let a = sa + da * (1.0 - sa);
+            if a > 0.0 {
+                for (d, s) in dst.iter_mut().zip(&[src.r, src.g, src.b]) {
+                    *d = (*s as f64 * sa + *d * da * (1.0 - sa)) / a;
+                }
+            }
+            dst[3] = a;
+        }
+    }
+
+    let img = resvg::render_with_options(&tree, usvg::FitTo::Original, None, &opt).unwrap();
+    for (c1, c2) in img.data().as_rgba().iter().zip(composite.chunks(4)) {
+        let c2 = rgb::RGBA8::new(
+            c2[0].round() as u8, c2[1].round() as u8, c2[2].round() as u8, (c2[3] * 255.0).round() as u8
+        );
+        // Layers are demultiplied separately, so rounding errors are bigger.
+        assert!((c1.r as i32 - c2.r as i32).abs() <= 3 &&
+                (c1.g as i32 - c2.g as i32).abs() <= 3 &&
+                (c1.b as i32 - c2.b as i32).abs() <= 3 &&
+                (c1.a as i32 - c2.a as i32).abs() <= 1, "{:?} != {:?}", c1, c2);
+    }
+
+    let layers = resvg::render_layers(&tree, Some(&["g2"]), usvg::FitTo::Original, None, &opt);
+    assert_eq!(layers.len(), 1);
+    assert_eq!(layers[0].0, "g2");
+}
+
+#[test]
+fn render_task() {
+    let svg = "
+<svg viewBox='0 0 200 200' xmlns='http://www.w3.org/2000/svg'>
+    <linearGradient id='lg1'>
+        <stop offset='0' stop-color='white'/>
+        <stop offset='1' stop-color='green'/>
+    </linearGradient>
+    <rect x='20' y='20' width='100' height='100' fill='url(#lg1)'/>
+    <circle cx='120' cy='120' r='60' fill='blue' opacity='0.5'/>
+    <path d='M 20 180 L 180 20' stroke='black' stroke-width='5'/>
+</svg>
+";
+
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    let opt = resvg::RenderOptions::default();
+    let fit_to = usvg::FitTo::Width(300);
+    let bg = Some(usvg::Color::new(255, 255, 255));
+    let expected = resvg::render_with_options(&tree, fit_to, bg, &opt).unwrap();
+
+    let mut task = resvg::RenderTask::new(&tree, fit_to, bg, opt.clone()).unwrap();
+    while !task.step(1) {}
+    // Stepping a finished task is a no-op, even with a huge count.
+    assert!(task.step(usize::MAX));
+    assert_eq!(task.finish().unwrap().data(), expected.data());
+
+    let mut task = resvg::RenderTask::new(&tree, fit_to, bg, opt).unwrap();
+    assert!(!task.step(2));
+    assert!(task.step(usize::MAX));
+    assert_eq!(task.finish().unwrap().data(), expected.data());
+}
+
+#[test]
+fn render_budget() {
+    let svg = "
+<svg viewBox='0 0 200 200' xmlns='http://www.w3.org/2000/svg'>
+    <rect x='20' y='20' width='100' height='100' fill='green'/>
+    <rect x='150' y='150' width='100' height='100' fill='blue'/>
+</svg>
+";
+
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    let fit_to = usvg::FitTo::Original;
+    let max_pixels = 200 * 200 * 2;
+
+    let opt = resvg::RenderOptions::default();
+    assert!(resvg::try_render(&tree, fit_to, None, &opt, max_pixels).is_ok());
+
+    // A supersampled image is rendered at a bigger size first.
+    let opt = resvg::RenderOptions::builder().supersample(4).build();
+    assert_eq!(resvg::compute_render_size_with_options(&tree, fit_to, &opt),
+               usvg::ScreenSize::new(200, 200));
+    assert_eq!(resvg::try_render(&tree, fit_to, None, &opt, max_pixels).err(),
+               Some(resvg::RenderError::ImageTooBig));
+    let (img, scale) = resvg::try_render_within_budget(&tree, fit_to, None, &opt, max_pixels).unwrap();
+    assert!(img.width() as u64 * img.height() as u64 * 16 <= max_pixels);
+    assert!(scale < 0.5);
+
+    let opt = resvg::RenderOptions::builder().padding(10).ignore_root_overflow(true).build();
+    assert_eq!(resvg::compute_render_size_with_options(&tree, fit_to, &opt),
+               usvg::ScreenSize::new(270, 270));
+    assert_eq!(resvg::try_render(&tree, fit_to, None, &opt, 260 * 260).err(),
+               Some(resvg::RenderError::ImageTooBig));
+    let (img, _) = resvg::try_render_within_budget(&tree, fit_to, None, &opt, 260 * 260).unwrap();
+    assert!(img.width() * img.height() <= 260 * 260);
+}
+
+#[test]
+fn progress_with_nested_svg() {
+    use std::sync::{Arc, Mutex};
+
+    let svg = "
+<svg viewBox='0 0 200 200' xmlns='http://www.w3.org/2000/svg'
+     xmlns:xlink='http://www.w3.org/1999/xlink'>
+    <rect x='20' y='20' width='50' height='50' fill='green'/>
+    <image x='20' y='80' width='100' height='100' xlink:href='image.svg'/>
+    <rect x='130' y='20' width='50' height='50' fill='blue'/>
+</svg>
+";
+
+    let uopt = usvg::Options { path: Some("tests/images/test.svg".into()), ..usvg::Options::default() };
+    let tree = usvg::Tree::from_str(svg, &uopt).unwrap();
+
+    let values = Arc::new(Mutex::new(Vec::new()));
+    let values2 = values.clone();
+    let opt = resvg::RenderOptions::builder()
+        .progress(move |v| values2.lock().unwrap().push(v))
+        .build();
+    resvg::render_with_options(&tree, usvg::FitTo::Original, None, &opt).unwrap();
+
+    let values = values.lock().unwrap();
+    assert_eq!(values.len(), 3);
+    assert!(values.windows(2).all(|w| w[0] <= w[1]), "{:?}", values);
+    assert_eq!(values.last(), Some(&1.0));
+}
+
+#[test]
+fn tree_to_polygons() {
+    let svg = "
+<svg viewBox='0 0 100 100' width='200' height='200' xmlns='http://www.w3.org/2000/svg'>
+    <circle id='circle1' cx='50' cy='50' r='40' stroke='black' stroke-width='10'/>
+    <circle id='circle2' cx='50' cy='50' r='40' fill='none' stroke='black'/>
+</svg>
+";
+
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    for tolerance in &[0.0, -1.0, f64::NAN, f64::INFINITY] {
+        assert!(resvg::tree_to_polygons(&tree, *tolerance).is_none());
+    }
+
+    // Strokes are ignored.
+    let paths = resvg::tree_to_polygons(&tree, 0.1).unwrap();
+    assert_eq!(paths.len(), 1);
+    assert_eq!(paths[0].id, "circle1");
+    assert_eq!(paths[0].polygons.len(), 1);
+
+    // Points are in the root user space.
+    for &(x, y) in &paths[0].polygons[0] {
+        let d = (x - 50.0).hypot(y - 50.0);
+        assert!(d > 39.8 && d < 40.2, "{}", d);
+    }
+}
+
+#[test]
+fn supersample() {
+    let svg = "
+<svg viewBox='0 0 200 200' xmlns='http://www.w3.org/2000/svg'>
+    <circle cx='100' cy='100' r='70' fill='green' shape-rendering='crispEdges'/>
+</svg>
+";
+
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    let fit_to = usvg::FitTo::Width(150);
+
+    // Counts edge pixels with a partial coverage.
+    let count_edge_levels = |img: &resvg::Image| {
+        let mut levels: Vec<u8> = img.data().as_rgba().iter()
+            .map(|c| c.a).filter(|a| *a != 0 && *a != 255).collect();
+        levels.sort();
+        levels.dedup();
+        levels.len()
+    };
+
+    let img = resvg::render_with_options(&tree, fit_to, None, &resvg::RenderOptions::default()).unwrap();
+    assert_eq!(count_edge_levels(&img), 0);
+
+    // Supersampling smooths edges even without anti-aliasing, but keeps the requested size.
+    let opt = resvg::RenderOptions::builder().supersample(4).build();
+    let img = resvg::render_with_options(&tree, fit_to, None, &opt).unwrap();
+    assert_eq!((img.width(), img.height()), (150, 150));
+    assert!(count_edge_levels(&img) > 4);
+
+    // Has no effect in the pixelated mode.
+    let opt = resvg::RenderOptions::builder().supersample(4).pixelated(true).build();
+    let img = resvg::render_with_options(&tree, fit_to, None, &opt).unwrap();
+    assert_eq!((img.width(), img.height()), (150, 150));
+    assert_eq!(count_edge_levels(&img), 0);
+}
+
+#[test]
+fn flip_y() {
+    let svg = "
+<svg viewBox='0 0 200 200' xmlns='http://www.w3.org/2000/svg'>
+    <filter id='filter1' x='0' y='0' width='1' height='10'>
+        <feOffset dy='100'/>
+    </filter>
+    <rect x='0' y='0' width='200' height='50' fill='green'/>
+    <rect x='0' y='0' width='200' height='20' fill='blue' filter='url(#filter1)'/>
+</svg>
+";
+
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    let fit_to = usvg::FitTo::Original;
+    let pixel = |img: &resvg::Image, x: u32, y: u32| img.data().as_rgba()[(y * img.width() + x) as usize];
+
+    let img = resvg::render(&tree, fit_to, None).unwrap();
+    let opt = resvg::RenderOptions::builder().flip_y(true).build();
+    let flipped = resvg::render_with_options(&tree, fit_to, None, &opt).unwrap();
+    let rows: Vec<&[u8]> = img.data().chunks(200 * 4).rev().collect();
+    assert_eq!(flipped.data(), &rows.concat()[..]);
+    assert_eq!(pixel(&flipped, 100, 175), rgb::RGBA8::new(0, 128, 0, 255));
+    assert_eq!(pixel(&flipped, 100, 90), rgb::RGBA8::new(0, 0, 255, 255));
+
+    // Post-processing is done in the image coordinates and is not flipped.
+    let mut clip = usvg::PathData::new();
+    clip.push_rect(usvg::Rect::new(0.0, 0.0, 200.0, 100.0).unwrap());
+    let opt = resvg::RenderOptions::builder().flip_y(true).clip(clip).padding(10).build();
+    let img = resvg::render_with_options(&tree, fit_to, None, &opt).unwrap();
+    assert_eq!(pixel(&img, 110, 100), rgb::RGBA8::new(0, 0, 255, 255));
+    assert_eq!(pixel(&img, 110, 185).a, 0);
+    assert_eq!(pixel(&img, 5, 100).a, 0);
+}
+
+#[test]
+fn save_png_with_options() {
+    let svg = "
+<svg viewBox='0 0 20 20' xmlns='http://www.w3.org/2000/svg'>
+    <rect x='0' y='0' width='10' height='20' fill='green' fill-opacity='0.5'/>
+</svg>
+";
+
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    let img = resvg::render(&tree, usvg::FitTo::Original, None).unwrap();
+
+    // Returns all chunks, except the image data ones.
+    let save = |opt: &resvg::PngOptions| {
+        let path = std::env::temp_dir().join(format!("resvg-png-options-{}.png", opt.srgb));
+        img.save_png_with_options(&path, opt).unwrap();
+        assert_eq!(load_png(&path), img.data());
+
+        let data = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let mut chunks = Vec::new();
+        let mut data = &data[8..];
+        while !data.is_empty() {
+            let len = u32::from_be_bytes([data[0], data[1], data[2], data[3]]) as usize;
+            let name = String::from_utf8(data[4..8].to_vec()).unwrap();
+            if name != "IDAT" {
+                chunks.push((name, data[8..8 + len].to_vec()));
+            }
+            data = &data[12 + len..];
+        }
+        chunks
+    };
+
+    let chunks = save(&resvg::PngOptions::default());
+    let names: Vec<_> = chunks.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["IHDR", "IEND"]);
+
+    let chunks = save(&resvg::PngOptions { srgb: true });
+    let names: Vec<_> = chunks.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["IHDR", "sRGB", "gAMA", "cHRM", "IEND"]);
+    assert_eq!(chunks[1].1, [0]);
+    assert_eq!(chunks[2].1, 45455u32.to_be_bytes());
+
+    let chrm: Vec<u32> = chunks[3].1.chunks(4)
+        .map(|c| u32::from_be_bytes([c[0], c[1], c[2], c[3]])).collect();
+    assert_eq!(chrm, [31270, 32900, 64000, 33000, 30000, 60000, 15000, 6000]);
+}
+
+#[test]
+fn paint_hook() {
+    use usvg::NodeExt;
+
+    let svg = "
+<svg viewBox='0 0 200 200' xmlns='http://www.w3.org/2000/svg'>
+    <rect id='rect1' x='20' y='20' width='160' height='60' fill='green' stroke='black' stroke-width='10'/>
+    <path id='path1' d='M 20 150 L 180 150' fill='none' stroke='black' stroke-width='20'/>
+</svg>
+";
+
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+
+    let calls = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let calls2 = calls.clone();
+    let opt = resvg::RenderOptions::builder()
+        .paint_hook(move |node, kind, paint| {
+            calls2.lock().unwrap().push((node.id().to_string(), kind));
+            if kind == resvg::PaintKind::Fill {
+                paint.set_color(0, 0, 255, 255);
+            }
+        })
+        .build();
+
+    let img = resvg::render_with_options(&tree, usvg::FitTo::Original, None, &opt).unwrap();
+    assert_eq!(*calls.lock().unwrap(), [
+        ("rect1".to_string(), resvg::PaintKind::Fill),
+        ("rect1".to_string(), resvg::PaintKind::Stroke),
+        ("path1".to_string(), resvg::PaintKind::Stroke),
+    ]);
+
+    // The hook result is used only for fills.
+    let pixel = |x: u32, y: u32| img.data().as_rgba()[(y * img.width() + x) as usize];
+    assert_eq!(pixel(100, 50), rgb::RGBA8::new(0, 0, 255, 255));
+    assert_eq!(pixel(20, 50), rgb::RGBA8::new(0, 0, 0, 255));
+    assert_eq!(pixel(100, 150), rgb::RGBA8::new(0, 0, 0, 255));
+}
+
+#[test]
+fn render_diff() {
+    let svg1 = "
+<svg viewBox='0 0 100 100' xmlns='http://www.w3.org/2000/svg'>
+    <rect x='0' y='0' width='50' height='100' fill='green'/>
+</svg>
+";
+    let svg2 = "
+<svg viewBox='0 0 100 100' xmlns='http://www.w3.org/2000/svg'>
+    <rect x='0' y='0' width='50' height='100' fill='green'/>
+    <rect x='50' y='0' width='10' height='10' fill='blue'/>
+</svg>
+";
+
+    let tree1 = usvg::Tree::from_str(svg1, &usvg::Options::default()).unwrap();
+    let tree2 = usvg::Tree::from_str(svg2, &usvg::Options::default()).unwrap();
+    let opt = resvg::RenderOptions::default();
+    let fit_to = usvg::FitTo::Original;
+
+    let (img, stats) = resvg::render_diff(&tree1, &tree1, fit_to, &opt, 1.0).unwrap();
+    assert_eq!(stats, resvg::DiffStats { max_diff: 0, mean_diff: 0.0, diff_pixels: 0.0 });
+    assert!(img.data().as_rgba().iter().all(|c| *c == rgb::RGBA8::new(0, 0, 0, 255)));
+
+    // 100 of 10000 pixels differ by the blue and alpha channels.
+    let (img, stats) = resvg::render_diff(&tree1, &tree2, fit_to, &opt, 1.0).unwrap();
+    assert_eq!(stats.max_diff, 255);
+    assert_eq!(stats.mean_diff, 510.0 * 100.0 / 40000.0);
+    assert_eq!(stats.diff_pixels, 1.0);
+
+    let pixels = img.data().as_rgba();
+    let white = rgb::RGBA8::new(255, 255, 255, 255);
+    assert_eq!(pixels.iter().filter(|c| **c == white).count(), 100);
+    assert_eq!(pixels[5 * 100 + 55], white);
+    assert_eq!(pixels[5 * 100 + 45], rgb::RGBA8::new(0, 0, 0, 255));
+
+    let img1 = resvg::render(&tree1, fit_to, None).unwrap();
+    let img2 = resvg::render(&tree2, usvg::FitTo::Width(50), None).unwrap();
+    assert!(img1.diff(&img2, 1.0).is_none());
+}
+
+#[test]
+fn image_cache() {
+    let svg = "
+<svg viewBox='0 0 200 200' xmlns='http://www.w3.org/2000/svg'
+     xmlns:xlink='http://www.w3.org/1999/xlink'>
+    <image x='20' y='20' width='60' height='60' xlink:href='image.png'/>
+    <image x='120' y='20' width='40' height='40' xlink:href='image.png'/>
+    <image x='20' y='120' width='60' height='60' xlink:href='image-63x61.png'/>
+    <image x='120' y='120' width='60' height='60' xlink:href='image.jpg'/>
+</svg>
+";
+
+    let uopt = usvg::Options { path: Some("tests/images/test.svg".into()), ..usvg::Options::default() };
+    let tree = usvg::Tree::from_str(svg, &uopt).unwrap();
+    let fit_to = usvg::FitTo::Original;
+    let expected = resvg::render(&tree, fit_to, None).unwrap();
+
+    let cache = resvg::ImageCache::new();
+    let opt = resvg::RenderOptions::builder().image_cache(cache.clone()).build();
+
+    // The same image is decoded once, while different images have separate entries.
+    let img = resvg::render_with_options(&tree, fit_to, None, &opt).unwrap();
+    assert_eq!(img.data(), expected.data());
+    assert_eq!(cache.len(), 3);
+
+    // The second render reuses the cached images.
+    let img = resvg::render_with_options(&tree, fit_to, None, &opt).unwrap();
+    assert_eq!(img.data(), expected.data());
+    assert_eq!(cache.len(), 3);
+
+    // Even for another tree.
+    let svg2 = svg.replace("image-63x61.png", "image.png");
+    let tree2 = usvg::Tree::from_str(&svg2, &uopt).unwrap();
+    resvg::render_with_options(&tree2, fit_to, None, &opt).unwrap();
+    assert_eq!(cache.len(), 3);
+
+    cache.clear();
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn render_atlas() {
+    let green = "
+<svg viewBox='0 0 20 20' xmlns='http://www.w3.org/2000/svg'>
+    <rect x='0' y='0' width='20' height='20' fill='green'/>
+</svg>
+";
+    let blue = "
+<svg viewBox='0 0 20 20' xmlns='http://www.w3.org/2000/svg'>
+    <rect x='0' y='0' width='20' height='20' fill='blue'/>
+</svg>
+";
+
+    let green = usvg::Tree::from_str(green, &usvg::Options::default()).unwrap();
+    let blue = usvg::Tree::from_str(blue, &usvg::Options::default()).unwrap();
+    let items = [
+        resvg::AtlasItem { tree: &green, fit_to: usvg::FitTo::Original, x: 0, y: 0 },
+        resvg::AtlasItem { tree: &blue, fit_to: usvg::FitTo::Width(10), x: 30, y: 5 },
+        // Partially outside the atlas.
+        resvg::AtlasItem { tree: &green, fit_to: usvg::FitTo::Original, x: 55, y: 55 },
+    ];
+    let size = usvg::ScreenSize::new(64, 64).unwrap();
+    let pixel = |img: &resvg::Image, x: u32, y: u32| img.data().as_rgba()[(y * img.width() + x) as usize];
+    let green = rgb::RGBA8::new(0, 128, 0, 255);
+    let blue = rgb::RGBA8::new(0, 0, 255, 255);
+
+    let opt = resvg::RenderOptions::default();
+    let (img, rects) = resvg::render_atlas(&items, size, None, &opt).unwrap();
+    assert_eq!((img.width(), img.height()), (64, 64));
+    assert_eq!(rects, [
+        usvg::ScreenRect::new(0, 0, 20, 20).unwrap(),
+        usvg::ScreenRect::new(30, 5, 10, 10).unwrap(),
+        usvg::ScreenRect::new(55, 55, 20, 20).unwrap(),
+    ]);
+    assert_eq!(pixel(&img, 10, 10), green);
+    assert_eq!(pixel(&img, 35, 10), blue);
+    assert_eq!(pixel(&img, 35, 20).a, 0);
+    assert_eq!(pixel(&img, 25, 25).a, 0);
+    assert_eq!(pixel(&img, 63, 63), green);
+
+    // The whole atlas is flipped.
+    let opt = resvg::RenderOptions::builder().flip_y(true).build();
+    let (img, rects) = resvg::render_atlas(&items, size, None, &opt).unwrap();
+    assert_eq!(rects[1], usvg::ScreenRect::new(30, 49, 10, 10).unwrap());
+    assert_eq!(pixel(&img, 10, 53), green);
+    assert_eq!(pixel(&img, 35, 53), blue);
+    assert_eq!(pixel(&img, 63, 0), green);
+}
+
+#[test]
+fn render_nodes() {
+    let svg = "
+<svg viewBox='0 0 200 200' xmlns='http://www.w3.org/2000/svg'
+     xmlns:xlink='http://www.w3.org/1999/xlink'>
+    <rect id='rect1' x='20' y='20' width='50' height='80' fill='green'/>
+    <image id='image1' x='100' y='20' width='80' height='80' xlink:href='image.png'/>
+    <circle id='circle1' cx='100' cy='150' r='30' fill='blue'/>
+</svg>
+";
+
+    let uopt = usvg::Options { path: Some("tests/images/test.svg".into()), ..usvg::Options::default() };
+    let tree = usvg::Tree::from_str(svg, &uopt).unwrap();
+    let opt = resvg::RenderOptions::default();
+    let fit_to = usvg::FitTo::Zoom(2.0);
+
+    let images = resvg::render_nodes(&tree, &["circle1", "missing", "image1", "rect1"], fit_to, None, &opt);
+    let ids: Vec<_> = images.iter().map(|(id, _)| id.as_str()).collect();
+    assert_eq!(ids, ["circle1", "image1", "rect1"]);
+
+    for (id, img) in &images {
+        let node = tree.node_by_id(id).unwrap();
+        let expected = resvg::render_node_with_options(&node, fit_to, None, &opt).unwrap();
+        assert_eq!((img.width(), img.height()), (expected.width(), expected.height()));
+        assert_eq!(img.data(), expected.data(), "{}", id);
+    }
+}
+
+#[test]
+fn turbulence_seed_offset() {
+    let svg = "
+<svg viewBox='0 0 200 200' xmlns='http://www.w3.org/2000/svg'>
+    <filter id='filter1'>
+        <feTurbulence baseFrequency='0.05' seed='5'/>
+    </filter>
+    <rect x='20' y='20' width='160' height='160' filter='url(#filter1)'/>
+</svg>
+";
+
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    let render = |opt: &resvg::RenderOptions| {
+        resvg::render_with_options(&tree, usvg::FitTo::Original, None, opt).unwrap()
+    };
+
+    let expected = render(&resvg::RenderOptions::default());
+    let img = render(&resvg::RenderOptions::builder().turbulence_seed_offset(0).build());
+    assert_eq!(img.data(), expected.data());
+
+    let img = render(&resvg::RenderOptions::builder().turbulence_seed_offset(3).build());
+    assert_ne!(img.data(), expected.data());
+
+    // The seed must not overflow.
+    render(&resvg::RenderOptions::builder().turbulence_seed_offset(i32::MAX).build());
+}
+
+/// Renders stroke outlines from `tree_to_paths` and the original stroke
+/// and returns the number of pixels with a different coverage.
+fn outline_diff(shape: &str) -> usize {
+    let svg = format!("<svg viewBox='0 0 200 200' xmlns='http://www.w3.org/2000/svg'>{}</svg>", shape);
+    let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+
+    let mut d = String::new();
+    for path in resvg::tree_to_paths(&tree, true) {
+        assert_eq!(path.fill_rule, usvg::FillRule::NonZero);
+        for seg in path.data.iter() {
+            match *seg {
+                usvg::PathSegment::MoveTo { x, y } => d.push_str(&format!("M {} {} ", x, y)),
+                usvg::PathSegment::LineTo { x, y } => d.push_str(&format!("L {} {} ", x, y)),
+                usvg::PathSegment::ClosePath => d.push_str("Z "),
+                usvg::PathSegment::CurveTo { .. } => unreachable!(),
+            }
+        }
+    }
+
+    let outline_svg = format!(
+        "<svg viewBox='0 0 200 200' xmlns='http://www.w3.org/2000/svg'><path d='{}'/></svg>", d
+    );
+    let outline_tree = usvg::Tree::from_str(&outline_svg, &usvg::Options::default()).unwrap();
+
+    let img1 = resvg::render(&tree, usvg::FitTo::Original, None).unwrap();
+    let img2 = resvg::render(&outline_tree, usvg::FitTo::Original, None).unwrap();
+
+    // Ignore anti-aliasing differences.
+    img1.data().as_rgba().iter().zip(img2.data().as_rgba())
+        .filter(|(a, b)| (a.a as i32 - b.a as i32).abs() > 127)
+        .count()
+}
+
+#[test]
+fn tree_to_paths_strokes() {
+    let stroke = "fill='none' stroke='black' stroke-width='16'";
+
+    // Caps.
+    for cap in &["butt", "round", "square"] {
+        let shape = format!("<path d='M 30 100 L 170 60' {} stroke-linecap='{}'/>", stroke, cap);
+        assert!(outline_diff(&shape) < 10, "{}", shape);
+    }
+
+    // Joins.
+    for join in &["miter", "round", "bevel"] {
+        let shape = format!("<path d='M 20 180 L 60 40 L 100 160 L 180 20' {} stroke-linejoin='{}'/>",
+                            stroke, join);
+        assert!(outline_diff(&shape) < 10, "{}", shape);
+    }
+
+    // A miter limit fallback and a closed subpath.
+    let shape = format!("<path d='M 20 20 L 180 40 L 20 60 Z' {} stroke-miterlimit='2'/>", stroke);
+    assert!(outline_diff(&shape) < 10, "{}", shape);
+
+    // Curves.
+    let shape = format!("<circle cx='100' cy='100' r='70' {}/>", stroke);
+    assert!(outline_diff(&shape) < 20, "{}", shape);
+}
+
+#[test]
+fn tree_to_paths_dashes() {
+    let stroke = "fill='none' stroke='black' stroke-width='10'";
+
+    let shape = format!("<path d='M 20 100 L 180 100' {} stroke-dasharray='20 10' stroke-dashoffset='5'/>",
+                        stroke);
+    assert!(outline_diff(&shape) < 10, "{}", shape);
+
+    let shape = format!("<path d='M 20 20 L 180 20 L 180 180' {} stroke-dasharray='30 15' \
+                         stroke-linejoin='round' stroke-linecap='square'/>", stroke);
+    assert!(outline_diff(&shape) < 10, "{}", shape);
+
+    // Dash ends on curves can be slightly off, since we measure the flattened circle.
+    let shape = format!("<circle cx='100' cy='100' r='70' {} stroke-dasharray='25 10'/>", stroke);
+    assert!(outline_diff(&shape) < 30, "{}", shape);
+
+    // Zero-length dashes are painted only by caps.
+    let svg = "
+<svg viewBox='0 0 200 200' xmlns='http://www.w3.org/2000/svg'>
+    <path id='round' d='M 20 50 L 180 50' fill='none' stroke='black' stroke-width='10'
+          stroke-dasharray='0 20' stroke-linecap='round'/>
+    <path id='square' d='M 20 100 L 180 100' fill='none' stroke='black' stroke-width='10'
+          stroke-dasharray='0 20' stroke-linecap='square'/>
+    <path id='butt' d='M 20 150 L 180 150' fill='none' stroke='black' stroke-width='10'
+          stroke-dasharray='0 20'/>
+</svg>
+";
+
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default()).unwrap();
+    let paths = resvg::tree_to_paths(&tree, true);
+    let ids: Vec<_> = paths.iter().map(|p| p.id.as_str()).collect();
+    assert_eq!(ids, ["round", "square"]);
+    for path in &paths {
+        // A dot every 20 units, except at the end, like Skia.
+        for i in 0..8 {
+            let x = 20.0 + i as f64 * 20.0;
+            let y = if path.id == "round" { 50.0 } else { 100.0 };
+            assert!(contains(&path.data, path.fill_rule, x, y), "{} {}", path.id, x);
+            assert!(!contains(&path.data, path.fill_rule, x + 10.0, y), "{} {}", path.id, x);
+        }
+    }
+
+    for s in &["round", "square"] {
+        let shape = format!("<path d='M 20 100 L 180 100' {} stroke-dasharray='0 20' stroke-linecap='{}'/>",
+                            stroke, s);
+        assert!(outline_diff(&shape) < 10, "{}", shape);
+    }
+}
@@ -0,0 +1,40 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+fn count_paints(opacity: &str) -> usize {
+    let svg = format!("
+<svg viewBox='0 0 200 200' xmlns='http://www.w3.org/2000/svg'>
+    <linearGradient id='lg1'>
+        <stop offset='0' stop-color='white'/>
+        <stop offset='1' stop-color='green'/>
+    </linearGradient>
+    <pattern id='patt1' patternUnits='userSpaceOnUse' width='20' height='20'>
+        <rect x='0' y='0' width='10' height='10' fill='blue'/>
+    </pattern>
+    <rect id='rect1' x='20' y='20' width='160' height='160'
+          fill='url(#lg1)' fill-opacity='{0}'
+          stroke='url(#patt1)' stroke-width='10' stroke-opacity='{0}'/>
+</svg>
+", opacity);
+
+    let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+
+    let count = Arc::new(AtomicUsize::new(0));
+    let count2 = count.clone();
+    let opt = resvg::RenderOptions::builder()
+        .paint_hook(move |node, _, _| {
+            if &*usvg::NodeExt::id(node) == "rect1" {
+                count2.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+        .build();
+
+    resvg::render_with_options(&tree, usvg::FitTo::Original, None, &opt).unwrap();
+    count.load(Ordering::SeqCst)
+}
+
+#[test]
+fn zero_opacity_paint_is_skipped() {
+    assert_eq!(count_paints("0"), 0);
+    assert_eq!(count_paints("0.5"), 2);
+}
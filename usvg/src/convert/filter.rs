@@ -94,6 +94,7 @@ fn collect_children(
         let kind = match try_opt_continue!(child.tag_name()) {
             EId::FeGaussianBlur => convert_fe_gaussian_blur(child, &primitives),
             EId::FeOffset => convert_fe_offset(child, &primitives, state),
+            EId::FeDropShadow => convert_fe_drop_shadow(child, &primitives, state),
             EId::FeBlend => convert_fe_blend(child, &primitives),
             EId::FeFlood => convert_fe_flood(child),
             EId::FeComposite => convert_fe_composite(child, &primitives),
@@ -146,7 +147,19 @@ fn convert_fe_gaussian_blur(
     fe: svgtree::Node,
     primitives: &[tree::FilterPrimitive],
 ) -> tree::FilterKind {
-    let text = fe.attribute::<&str>(AId::StdDeviation).unwrap_or("0 0");
+    let (std_dev_x, std_dev_y) = convert_std_dev(fe, "0 0");
+    tree::FilterKind::FeGaussianBlur(tree::FeGaussianBlur {
+        input: resolve_input(fe, AId::In, primitives),
+        std_dev_x,
+        std_dev_y,
+    })
+}
+
+fn convert_std_dev(
+    fe: svgtree::Node,
+    default: &str,
+) -> (tree::PositiveNumber, tree::PositiveNumber) {
+    let text = fe.attribute::<&str>(AId::StdDeviation).unwrap_or(default);
     let mut parser = svgtypes::NumberListParser::from(text);
 
     let n1 = parser.next().and_then(|n| n.ok());
@@ -164,11 +177,7 @@ fn convert_fe_gaussian_blur(
     if std_dev_x.is_sign_negative() { std_dev_x = 0.0; }
     if std_dev_y.is_sign_negative() { std_dev_y = 0.0; }
 
-    tree::FilterKind::FeGaussianBlur(tree::FeGaussianBlur {
-        input: resolve_input(fe, AId::In, primitives),
-        std_dev_x: std_dev_x.into(),
-        std_dev_y: std_dev_y.into(),
-    })
+    (std_dev_x.into(), std_dev_y.into())
 }
 
 fn convert_fe_offset(
@@ -183,16 +192,44 @@ fn convert_fe_offset(
     })
 }
 
+fn convert_fe_drop_shadow(
+    fe: svgtree::Node,
+    primitives: &[tree::FilterPrimitive],
+    state: &State,
+) -> tree::FilterKind {
+    let (std_dev_x, std_dev_y) = convert_std_dev(fe, "2 2");
+    tree::FilterKind::FeDropShadow(tree::FeDropShadow {
+        input: resolve_input(fe, AId::In, primitives),
+        dx: fe.convert_user_length(AId::Dx, state, Length::new_number(2.0)),
+        dy: fe.convert_user_length(AId::Dy, state, Length::new_number(2.0)),
+        std_dev_x,
+        std_dev_y,
+        color: fe.attribute(AId::FloodColor).unwrap_or_else(tree::Color::black),
+        opacity: fe.attribute(AId::FloodOpacity).unwrap_or_default(),
+    })
+}
+
 fn convert_fe_blend(
     fe: svgtree::Node,
     primitives: &[tree::FilterPrimitive],
 ) -> tree::FilterKind {
     let mode = match fe.attribute(AId::Mode).unwrap_or("normal") {
-        "multiply"  => tree::FeBlendMode::Multiply,
-        "screen"    => tree::FeBlendMode::Screen,
-        "darken"    => tree::FeBlendMode::Darken,
-        "lighten"   => tree::FeBlendMode::Lighten,
-        _           => tree::FeBlendMode::Normal,
+        "multiply"    => tree::FeBlendMode::Multiply,
+        "screen"      => tree::FeBlendMode::Screen,
+        "darken"      => tree::FeBlendMode::Darken,
+        "lighten"     => tree::FeBlendMode::Lighten,
+        "overlay"     => tree::FeBlendMode::Overlay,
+        "color-dodge" => tree::FeBlendMode::ColorDodge,
+        "color-burn"  => tree::FeBlendMode::ColorBurn,
+        "hard-light"  => tree::FeBlendMode::HardLight,
+        "soft-light"  => tree::FeBlendMode::SoftLight,
+        "difference"  => tree::FeBlendMode::Difference,
+        "exclusion"   => tree::FeBlendMode::Exclusion,
+        "hue"         => tree::FeBlendMode::Hue,
+        "saturation"  => tree::FeBlendMode::Saturation,
+        "color"       => tree::FeBlendMode::Color,
+        "luminosity"  => tree::FeBlendMode::Luminosity,
+        _             => tree::FeBlendMode::Normal,
     };
 
     let input1 = resolve_input(fe, AId::In, primitives);
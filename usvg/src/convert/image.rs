@@ -156,8 +156,12 @@ pub fn load_sub_svg(data: &[u8], opt: &Options) -> Option<tree::ImageKind> {
         text_rendering: opt.text_rendering,
         image_rendering: opt.image_rendering,
         keep_named_groups: false,
+        reject_unknown_elements: false,
+        flatten_transforms: false,
         #[cfg(feature = "text")]
         fontdb: opt.fontdb.clone(),
+        #[cfg(feature = "text")]
+        missing_text: opt.missing_text,
     };
 
     let tree = match tree::Tree::from_data(data, &sub_opt) {
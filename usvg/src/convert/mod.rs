@@ -2,6 +2,9 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
+#[cfg(feature = "text")] use std::cell::Cell;
+use std::rc::Rc;
+
 use svgtypes::Length;
 
 use crate::{svgtree, tree, tree::prelude::*, Error};
@@ -35,6 +38,8 @@ pub struct State<'a> {
     fe_image_link: bool,
     size: Size,
     view_box: Rect,
+    #[cfg(feature = "text")]
+    missing_fonts: &'a Cell<bool>,
     opt: &'a Options,
 }
 
@@ -45,10 +50,19 @@ pub struct State<'a> {
 ///
 /// - If `Document` doesn't have an SVG node - returns an empty tree.
 /// - If `Document` doesn't have a valid size - returns `Error::InvalidSize`.
+/// - If `Document` has unknown elements and `Options::reject_unknown_elements` is set
+///   - returns `Error::UnknownElements`.
 pub fn convert_doc(
     svg_doc: &svgtree::Document,
     opt: &Options,
 ) -> Result<tree::Tree, Error> {
+    if opt.reject_unknown_elements {
+        let names = svg_doc.unknown_elements();
+        if !names.is_empty() {
+            return Err(Error::UnknownElements(names));
+        }
+    }
+
     let svg = svg_doc.root_element();
     let size = resolve_svg_size(&svg, opt)?;
     let view_box = tree::ViewBox {
@@ -63,22 +77,37 @@ pub fn convert_doc(
         return Ok(tree);
     }
 
+    #[cfg(feature = "text")]
+    let missing_fonts = Cell::new(false);
     let state = State {
         parent_clip_path: None,
         parent_marker: None,
         fe_image_link: false,
         size,
         view_box: view_box.rect,
+        #[cfg(feature = "text")]
+        missing_fonts: &missing_fonts,
         opt: &opt,
     };
 
     convert_children(svg_doc.root(), &state, &mut tree.root(), &mut tree);
 
+    #[cfg(feature = "text")]
+    {
+        if missing_fonts.get() {
+            return Err(Error::MissingFonts);
+        }
+    }
+
     link_fe_image(svg_doc, &state, &mut tree);
     remove_empty_groups(&mut tree);
     ungroup_groups(opt, &mut tree);
     remove_unused_defs(&mut tree);
 
+    if opt.flatten_transforms {
+        flatten_transforms(&mut tree);
+    }
+
     Ok(tree)
 }
 
@@ -92,6 +121,8 @@ fn resolve_svg_size(
         fe_image_link: false,
         size: Size::new(100.0, 100.0).unwrap(),
         view_box: Rect::new(0.0, 0.0, 100.0, 100.0).unwrap(),
+        #[cfg(feature = "text")]
+        missing_fonts: &Cell::new(false),
         opt,
     };
 
@@ -470,6 +501,90 @@ fn remove_empty_groups(tree: &mut tree::Tree) {
     while rm(tree.root()) {}
 }
 
+/// Applies transforms to the paths geometry where it doesn't affect rendering.
+fn flatten_transforms(tree: &mut tree::Tree) {
+    fn flatten(parent: tree::Node, parent_ts: tree::Transform) {
+        for mut node in parent.children() {
+            let mut ts = node.transform();
+            ts.prepend(&parent_ts);
+
+            let mut children_ts = None;
+            match *node.borrow_mut() {
+                tree::NodeKind::Group(ref mut g) => {
+                    // Clip paths, masks and filters are defined in the group coordinates,
+                    // so the group content must stay in them.
+                    let is_barrier =    g.clip_path.is_some()
+                                     || g.mask.is_some()
+                                     || g.filter.is_some()
+                                     || g.enable_background.is_some();
+                    if is_barrier {
+                        g.transform = ts;
+                    } else {
+                        g.transform = tree::Transform::default();
+                        children_ts = Some(ts);
+                    }
+                }
+                tree::NodeKind::Path(ref mut path) => {
+                    flatten_path(path, ts);
+                }
+                tree::NodeKind::Image(ref mut img) => {
+                    img.transform = ts;
+                }
+                _ => {}
+            }
+
+            if let Some(ts) = children_ts {
+                flatten(node, ts);
+            }
+        }
+    }
+
+    flatten(tree.root(), tree::Transform::default());
+}
+
+/// Applies the `ts` to the path data.
+///
+/// When the path cannot be transformed without affecting rendering,
+/// the `ts` is set as the path transform instead.
+fn flatten_path(path: &mut tree::Path, ts: tree::Transform) {
+    fn is_color(paint: &tree::Paint) -> bool {
+        matches!(paint, tree::Paint::Color(_))
+    }
+
+    // Paint servers depend on the path coordinates and bounding box.
+    if path.vector_effect == tree::VectorEffect::NonScalingSize
+        || path.fill.as_ref().map(|f| !is_color(&f.paint)).unwrap_or(false)
+        || path.stroke.as_ref().map(|s| !is_color(&s.paint)).unwrap_or(false)
+    {
+        path.transform = ts;
+        return;
+    }
+
+    if let Some(ref mut stroke) = path.stroke {
+        // A stroke can only be scaled uniformly, so only rotation, translation
+        // and a proportional scale are allowed.
+        let sx = (ts.a * ts.a + ts.b * ts.b).sqrt();
+        let sy = (ts.c * ts.c + ts.d * ts.d).sqrt();
+        if !sx.fuzzy_eq(&sy) || !(ts.a * ts.c + ts.b * ts.d).is_fuzzy_zero() {
+            path.transform = ts;
+            return;
+        }
+
+        stroke.width = tree::StrokeWidth::new(stroke.width.value() * sx);
+        stroke.dashoffset *= sx as f32;
+        if let Some(ref mut list) = stroke.dasharray {
+            for n in list {
+                *n *= sx;
+            }
+        }
+    }
+
+    let mut data = (*path.data).clone();
+    data.transform(ts);
+    path.data = Rc::new(data);
+    path.transform = tree::Transform::default();
+}
+
 fn ungroup_groups(
     opt: &Options,
     tree: &mut tree::Tree,
@@ -694,35 +809,97 @@ fn convert_path(
     let rendering_mode = node
         .find_attribute(AId::ShapeRendering)
         .unwrap_or(state.opt.shape_rendering);
+    let vector_effect = node.attribute(AId::VectorEffect).unwrap_or_default();
+    let pointer_events: tree::PointerEvents = node.find_attribute(AId::PointerEvents).unwrap_or_default();
 
     // If a path doesn't have a fill or a stroke than it's invisible.
     // By setting `visibility` to `hidden` we are disabling the rendering of this path.
-    if fill.is_none() && stroke.is_none() {
+    //
+    // Unless it still can be hit by its geometry.
+    let is_painted = fill.is_some() || stroke.is_some();
+    if !is_painted && (pointer_events.requires_paint() || pointer_events == tree::PointerEvents::None) {
         visibility = tree::Visibility::Hidden;
     }
 
     let mut markers_group = None;
-    if marker::is_valid(node) && visibility == tree::Visibility::Visible {
+    if marker::is_valid(node) && is_painted && visibility == tree::Visibility::Visible {
         let mut g = parent.append_kind(tree::NodeKind::Group(tree::Group::default()));
         marker::convert(node, &path, state, &mut g, tree);
         markers_group = Some(g);
     }
 
-    parent.append_kind(tree::NodeKind::Path(tree::Path {
+    let paint_order = style::resolve_paint_order(node);
+
+    let path = tree::Path {
         id: node.element_id().to_string(),
         transform: Default::default(),
         visibility,
         fill,
         stroke,
         rendering_mode,
+        vector_effect,
+        paint_order: style::convert_paint_order(paint_order),
+        pointer_events,
         data: path,
-    }));
+    };
+
+    let mut markers_group = match markers_group {
+        Some(g) => g,
+        None => {
+            parent.append_kind(tree::NodeKind::Path(path));
+            return;
+        }
+    };
 
-    // Insert markers group after `path`.
-    if let Some(mut g) = markers_group {
-        g.detach();
-        parent.append(g);
+    match paint_order {
+        [style::PaintOrderKind::Markers, _, _] => {
+            // Markers group is already inserted before `path`.
+            parent.append_kind(tree::NodeKind::Path(path));
+        }
+        [first, style::PaintOrderKind::Markers, last] => {
+            // Markers are drawn between the fill and the stroke,
+            // so we have to split the path into two.
+            markers_group.detach();
+            let is_appended = append_single_paint_path(first, &path, true, parent);
+            parent.append(markers_group);
+            append_single_paint_path(last, &path, !is_appended, parent);
+        }
+        _ => {
+            // Insert markers group after `path`.
+            markers_group.detach();
+            parent.append_kind(tree::NodeKind::Path(path));
+            parent.append(markers_group);
+        }
+    }
+}
+
+/// Appends a copy of `path` with only a fill or a stroke.
+///
+/// Returns `false` when there is nothing to paint.
+fn append_single_paint_path(
+    kind: style::PaintOrderKind,
+    path: &tree::Path,
+    keep_id: bool,
+    parent: &mut tree::Node,
+) -> bool {
+    let mut path = path.clone();
+    match kind {
+        style::PaintOrderKind::Fill => path.stroke = None,
+        style::PaintOrderKind::Stroke => path.fill = None,
+        style::PaintOrderKind::Markers => return false,
+    }
+
+    if path.fill.is_none() && path.stroke.is_none() {
+        return false;
     }
+
+    // Only one of the copies should preserve the element ID, to keep IDs unique.
+    if !keep_id {
+        path.id.clear();
+    }
+
+    parent.append_kind(tree::NodeKind::Path(path));
+    true
 }
 
 
@@ -5,7 +5,7 @@
 use std::f64;
 
 use crate::{svgtree, tree, tree::prelude::*};
-use super::prelude::*;
+use super::{prelude::*, units};
 
 
 pub enum ServerOrColor {
@@ -53,16 +53,32 @@ fn convert_linear(
     }
 
     let units = convert_units(node, AId::GradientUnits, tree::Units::ObjectBoundingBox);
-    let transform = resolve_attr(node, AId::GradientTransform)
-        .attribute(AId::GradientTransform).unwrap_or_default();
+    let x1 = resolve_number(node, AId::X1, units, state, Length::zero());
+    let y1 = resolve_number(node, AId::Y1, units, state, Length::zero());
+    let x2 = resolve_number(node, AId::X2, units, state, Length::new(100.0, Unit::Percent));
+    let y2 = resolve_number(node, AId::Y2, units, state, Length::zero());
+
+    // 'If x1 = x2 and y1 = y2, then the area to be painted will be painted
+    // as a single color using the color and opacity of the last gradient stop.'
+    //
+    // https://www.w3.org/TR/SVG11/pservers.html#LinearGradientElementX2Attribute
+    if x1.fuzzy_eq(&x2) && y1.fuzzy_eq(&y2) {
+        let stop = stops.last().unwrap();
+        return Some(ServerOrColor::Color {
+            color: stop.color,
+            opacity: stop.opacity,
+        });
+    }
+
+    let transform = resolve_transform(node, AId::GradientTransform, units, state);
 
     tree.append_to_defs(
         tree::NodeKind::LinearGradient(tree::LinearGradient {
             id: node.element_id().to_string(),
-            x1: resolve_number(node, AId::X1, units, state, Length::zero()),
-            y1: resolve_number(node, AId::Y1, units, state, Length::zero()),
-            x2: resolve_number(node, AId::X2, units, state, Length::new(100.0, Unit::Percent)),
-            y2: resolve_number(node, AId::Y2, units, state, Length::zero()),
+            x1,
+            y1,
+            x2,
+            y2,
             base: tree::BaseGradient {
                 units,
                 transform,
@@ -110,8 +126,7 @@ fn convert_radial(
     let fx = resolve_number(node, AId::Fx, units, state, Length::new_number(cx));
     let fy = resolve_number(node, AId::Fy, units, state, Length::new_number(cy));
     let (fx, fy) = prepare_focal(cx, cy, r, fx, fy);
-    let transform = resolve_attr(node, AId::GradientTransform)
-        .attribute(AId::GradientTransform).unwrap_or_default();
+    let transform = resolve_transform(node, AId::GradientTransform, units, state);
 
     tree.append_to_defs(
         tree::NodeKind::RadialGradient(tree::RadialGradient {
@@ -158,8 +173,7 @@ fn convert_pattern(
     let units = convert_units(node, AId::PatternUnits, tree::Units::ObjectBoundingBox);
     let content_units = convert_units(node, AId::PatternContentUnits, tree::Units::UserSpaceOnUse);
 
-    let transform = resolve_attr(node, AId::PatternTransform)
-        .attribute(AId::PatternTransform).unwrap_or_default();
+    let transform = resolve_transform(node, AId::PatternTransform, units, state);
 
     let rect = Rect::new(
         resolve_number(node, AId::X, units, state, Length::zero()),
@@ -193,6 +207,87 @@ fn convert_pattern(
     })
 }
 
+/// Resolves a paint server transform with `transform-origin` applied.
+///
+/// `transform-origin` is taken from the same element as the transform
+/// and is resolved using the paint server units.
+fn resolve_transform(
+    node: svgtree::Node,
+    name: AId,
+    units: tree::Units,
+    state: &State,
+) -> tree::Transform {
+    let node = resolve_attr(node, name);
+    let ts: tree::Transform = node.attribute(name).unwrap_or_default();
+    if ts.is_default() {
+        return ts;
+    }
+
+    let (ox, oy) = match node.attribute::<&str>(AId::TransformOrigin) {
+        Some(text) => parse_transform_origin(text),
+        None => return ts,
+    };
+
+    let ox = units::convert_length(ox, node, AId::X, units, state);
+    let oy = units::convert_length(oy, node, AId::Y, units, state);
+
+    let mut origin_ts = tree::Transform::new_translate(ox, oy);
+    origin_ts.append(&ts);
+    origin_ts.translate(-ox, -oy);
+    origin_ts
+}
+
+/// Parses a `transform-origin` value.
+///
+/// The optional z offset is ignored and invalid values fallback to `0 0`.
+fn parse_transform_origin(text: &str) -> (Length, Length) {
+    fn parse_keyword(s: &str) -> Option<Length> {
+        match s {
+            "left" | "top" => Some(Length::new(0.0, Unit::Percent)),
+            "center" => Some(Length::new(50.0, Unit::Percent)),
+            "right" | "bottom" => Some(Length::new(100.0, Unit::Percent)),
+            _ => s.parse().ok(),
+        }
+    }
+
+    let is_vertical = |s: &str| s == "top" || s == "bottom";
+    let is_horizontal = |s: &str| s == "left" || s == "right";
+
+    let def = (Length::zero(), Length::zero());
+
+    let mut iter = text.split_whitespace();
+    let first = match iter.next() {
+        Some(v) => v,
+        None => return def,
+    };
+
+    let (x, y) = match iter.next() {
+        Some(second) => {
+            // Keywords can be specified in any order.
+            if is_vertical(first) || is_horizontal(second) {
+                (second, first)
+            } else {
+                (first, second)
+            }
+        }
+        None => {
+            if is_vertical(first) {
+                ("center", first)
+            } else {
+                (first, "center")
+            }
+        }
+    };
+
+    match (parse_keyword(x), parse_keyword(y)) {
+        (Some(x), Some(y)) => (x, y),
+        _ => {
+            warn!("Invalid transform-origin value: '{}'.", text);
+            def
+        }
+    }
+}
+
 fn convert_spread_method(node: svgtree::Node) -> tree::SpreadMethod {
     let node = resolve_attr(node, AId::SpreadMethod);
     node.attribute(AId::SpreadMethod).unwrap_or_default()
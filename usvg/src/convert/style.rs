@@ -74,6 +74,63 @@ pub fn resolve_stroke(
     Some(stroke)
 }
 
+/// A single `paint-order` operation.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PaintOrderKind {
+    Fill,
+    Stroke,
+    Markers,
+}
+
+/// Resolves `paint-order` into a full list of paint operations.
+///
+/// Operations that are not listed explicitly are appended in the default order.
+/// An invalid value fallbacks to the default order.
+pub fn resolve_paint_order(node: svgtree::Node) -> [PaintOrderKind; 3] {
+    const DEFAULT: [PaintOrderKind; 3] =
+        [PaintOrderKind::Fill, PaintOrderKind::Stroke, PaintOrderKind::Markers];
+
+    let value: &str = node.find_attribute(AId::PaintOrder).unwrap_or("normal");
+    if value.trim() == "normal" {
+        return DEFAULT;
+    }
+
+    let mut order = Vec::with_capacity(3);
+    for name in value.split_whitespace() {
+        let kind = match name {
+            "fill" => PaintOrderKind::Fill,
+            "stroke" => PaintOrderKind::Stroke,
+            "markers" => PaintOrderKind::Markers,
+            _ => return DEFAULT,
+        };
+
+        if order.contains(&kind) {
+            return DEFAULT;
+        }
+
+        order.push(kind);
+    }
+
+    for kind in &DEFAULT {
+        if !order.contains(kind) {
+            order.push(*kind);
+        }
+    }
+
+    [order[0], order[1], order[2]]
+}
+
+/// Converts paint operations into a fill and stroke order.
+pub fn convert_paint_order(order: [PaintOrderKind; 3]) -> tree::PaintOrder {
+    let fill_idx = order.iter().position(|k| *k == PaintOrderKind::Fill);
+    let stroke_idx = order.iter().position(|k| *k == PaintOrderKind::Stroke);
+    if stroke_idx < fill_idx {
+        tree::PaintOrder::StrokeAndFill
+    } else {
+        tree::PaintOrder::FillAndStroke
+    }
+}
+
 fn convert_paint(
     node: svgtree::Node,
     aid: AId,
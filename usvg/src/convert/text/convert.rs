@@ -5,7 +5,7 @@
 use std::cmp;
 use std::rc::Rc;
 
-use crate::{fontdb_ext, svgtree, tree, Transform};
+use crate::{fontdb_ext, svgtree, tree, MissingText, Transform};
 use crate::convert::{prelude::*, style, units};
 use crate::fontdb_ext::DatabaseExt;
 use super::TextNode;
@@ -115,11 +115,13 @@ pub struct TextSpan {
     pub end: usize,
     pub fill: Option<tree::Fill>,
     pub stroke: Option<tree::Stroke>,
+    pub paint_order: tree::PaintOrder,
     pub font: fontdb_ext::Font,
     pub font_size: f64,
     pub decoration: TextDecoration,
     pub baseline_shift: f64,
     pub visibility: tree::Visibility,
+    pub pointer_events: tree::PointerEvents,
     pub letter_spacing: f64,
     pub word_spacing: f64,
 }
@@ -138,12 +140,23 @@ pub enum WritingMode {
 }
 
 
+/// Text spans that were skipped, because no suitable fonts were found.
+pub struct MissingSpans {
+    pub chars_count: usize,
+    /// A font size of the first skipped span.
+    pub font_size: f64,
+    /// A fill of the first skipped span.
+    pub fill: Option<tree::Fill>,
+}
+
+
 struct IterState {
     chars_count: usize,
     chunk_bytes_count: usize,
     split_chunk: bool,
     text_flow: TextFlow,
     chunks: Vec<TextChunk>,
+    missing_spans: Option<MissingSpans>,
 }
 
 pub fn collect_text_chunks(
@@ -151,18 +164,19 @@ pub fn collect_text_chunks(
     pos_list: &[CharacterPosition],
     state: &State,
     tree: &mut tree::Tree,
-) -> Vec<TextChunk> {
+) -> (Vec<TextChunk>, Option<MissingSpans>) {
     let mut iter_state = IterState {
         chars_count: 0,
         chunk_bytes_count: 0,
         split_chunk: false,
         text_flow: TextFlow::Horizontal,
         chunks: Vec::new(),
+        missing_spans: None,
     };
 
     collect_text_chunks_impl(text_node, *text_node, pos_list, state, tree, &mut iter_state);
 
-    iter_state.chunks
+    (iter_state.chunks, iter_state.missing_spans)
 }
 
 fn collect_text_chunks_impl(
@@ -228,8 +242,22 @@ fn collect_text_chunks_impl(
         let font = match resolve_font(parent, state) {
             Some(v) => v,
             None => {
+                let chars_count = child.text().chars().count();
+                if state.opt.missing_text != MissingText::Skip {
+                    match iter_state.missing_spans {
+                        Some(ref mut spans) => spans.chars_count += chars_count,
+                        None => {
+                            iter_state.missing_spans = Some(MissingSpans {
+                                chars_count,
+                                font_size,
+                                fill: style::resolve_fill(parent, true, state, tree),
+                            });
+                        }
+                    }
+                }
+
                 // Skip this span.
-                iter_state.chars_count += child.text().chars().count();
+                iter_state.chars_count += chars_count;
                 continue;
             }
         };
@@ -239,10 +267,12 @@ fn collect_text_chunks_impl(
             end: 0,
             fill: style::resolve_fill(parent, true, state, tree),
             stroke: style::resolve_stroke(parent, true, state, tree),
+            paint_order: style::convert_paint_order(style::resolve_paint_order(parent)),
             font,
             font_size,
             decoration: resolve_decoration(text_node, parent, state, tree),
             visibility: parent.find_attribute(AId::Visibility).unwrap_or_default(),
+            pointer_events: parent.find_attribute(AId::PointerEvents).unwrap_or_default(),
             baseline_shift: resolve_baseline_shift(parent, state),
             letter_spacing: parent.resolve_length(AId::LetterSpacing, state, 0.0),
             word_spacing: parent.resolve_length(AId::WordSpacing, state, 0.0),
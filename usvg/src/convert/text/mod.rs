@@ -4,7 +4,7 @@
 
 use std::rc::Rc;
 
-use crate::{svgtree, tree, tree::prelude::*};
+use crate::{svgtree, tree, tree::prelude::*, MissingText};
 use super::prelude::*;
 
 mod convert;
@@ -102,7 +102,7 @@ fn text_to_paths(
     let writing_mode = convert_writing_mode(text_node);
     let mut text_ts = tree::Transform::default();
 
-    let mut chunks = collect_text_chunks(text_node, &pos_list, state, tree);
+    let (mut chunks, missing_spans) = collect_text_chunks(text_node, &pos_list, state, tree);
     let mut char_offset = 0;
     let mut last_x = 0.0;
     let mut last_y = 0.0;
@@ -191,9 +191,55 @@ fn text_to_paths(
         last_y = y + curr_pos.1;
     }
 
+    if let Some(spans) = missing_spans {
+        match state.opt.missing_text {
+            MissingText::Skip => {}
+            MissingText::PlaceholderBox => {
+                let x = pos_list.first().and_then(|p| p.x).unwrap_or(0.0);
+                let y = pos_list.first().and_then(|p| p.y).unwrap_or(0.0);
+                if let Some(path) = convert_placeholder_box(spans, x, y) {
+                    new_paths.push(path);
+                }
+            }
+            MissingText::Error => {
+                state.missing_fonts.set(true);
+            }
+        }
+    }
+
     new_paths
 }
 
+/// Creates a box that replaces a text without suitable fonts.
+///
+/// The box starts at the text position, uses the font size as a height
+/// and a half of it as a character width.
+fn convert_placeholder_box(
+    spans: MissingSpans,
+    x: f64,
+    y: f64,
+) -> Option<tree::Path> {
+    let rect = Rect::new(
+        x, y - spans.font_size,
+        spans.chars_count as f64 * spans.font_size * 0.5, spans.font_size,
+    )?;
+
+    let stroke = match spans.fill {
+        Some(fill) => tree::Stroke {
+            paint: fill.paint,
+            opacity: fill.opacity,
+            .. tree::Stroke::default()
+        },
+        None => tree::Stroke::default(),
+    };
+
+    Some(tree::Path {
+        stroke: Some(stroke),
+        data: Rc::new(tree::PathData::from_rect(rect)),
+        .. tree::Path::default()
+    })
+}
+
 fn convert_span(
     span: &mut TextSpan,
     clusters: &mut [OutlinedCluster],
@@ -245,6 +291,9 @@ fn convert_span(
         fill,
         stroke: span.stroke.take(),
         rendering_mode: tree::ShapeRendering::default(),
+        vector_effect: tree::VectorEffect::default(),
+        paint_order: span.paint_order,
+        pointer_events: span.pointer_events,
         data: Rc::new(path_data),
     };
 
@@ -355,6 +404,8 @@ fn convert_decoration(
         visibility: span.visibility,
         fill: decoration.fill.take(),
         stroke: decoration.stroke.take(),
+        paint_order: span.paint_order,
+        pointer_events: span.pointer_events,
         data: Rc::new(path),
         .. tree::Path::default()
     }
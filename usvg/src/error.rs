@@ -27,6 +27,18 @@ pub enum Error {
 
     /// Failed to parse an SVG data.
     ParsingFailed(roxmltree::Error),
+
+    /// No suitable fonts were found for a text.
+    ///
+    /// Occurs only when `Options::missing_text` is set to `MissingText::Error`.
+    MissingFonts,
+
+    /// SVG has unknown elements.
+    ///
+    /// Contains a sorted list of unique element names.
+    ///
+    /// Occurs only when `Options::reject_unknown_elements` is set to `true`.
+    UnknownElements(Vec<String>),
 }
 
 impl std::fmt::Display for Error {
@@ -50,6 +62,12 @@ impl std::fmt::Display for Error {
             Error::ParsingFailed(ref e) => {
                 write!(f, "SVG data parsing failed cause {}", e)
             }
+            Error::MissingFonts => {
+                write!(f, "no suitable fonts were found for a text")
+            }
+            Error::UnknownElements(ref names) => {
+                write!(f, "SVG has unknown elements: {}", names.join(", "))
+            }
         }
     }
 }
@@ -75,6 +75,7 @@ OPTIONS:
                                 Useful for debugging
 
   --keep-named-groups           Disables removing of groups with non-empty ID
+  --flatten-transforms          Applies transforms to the paths geometry
   --indent INDENT               Sets the XML nodes indent
                                 [values: none, 0, 1, 2, 3, 4, tabs] [default: 4]
   --attrs-indent INDENT         Sets the XML attributes indent
@@ -112,6 +113,7 @@ struct Args {
     list_fonts: bool,
 
     keep_named_groups: bool,
+    flatten_transforms: bool,
     indent: usvg::XmlIndent,
     attrs_indent: usvg::XmlIndent,
 
@@ -147,6 +149,7 @@ fn collect_args() -> Result<Args, pico_args::Error> {
         list_fonts:         input.contains("--list-fonts"),
 
         keep_named_groups:  input.contains("--keep-named-groups"),
+        flatten_transforms: input.contains("--flatten-transforms"),
         indent:             input.opt_value_from_fn("--indent", parse_indent)?
                                  .unwrap_or(usvg::XmlIndent::Spaces(4)),
         attrs_indent:       input.opt_value_from_fn("--attrs-indent", parse_indent)?
@@ -328,7 +331,10 @@ fn process(args: Args) -> Result<(), String> {
         text_rendering: args.text_rendering,
         image_rendering: args.image_rendering,
         keep_named_groups: args.keep_named_groups,
+        reject_unknown_elements: false,
+        flatten_transforms: args.flatten_transforms,
         fontdb,
+        missing_text: usvg::MissingText::Skip,
     };
 
     let input_str = match in_svg {
@@ -18,6 +18,16 @@ pub enum FitTo {
     Height(u32),
     /// Zoom by factor.
     Zoom(f32),
+    /// Scale to the physical size at the specified DPI.
+    ///
+    /// The SVG size is expected to be resolved at the default `Options::dpi`,
+    /// which is also the CSS pixel resolution of 96 DPI.
+    /// This way sizes in `mm`, `in` or `pt` are converted to their physical size
+    /// and sizes in `px` are treated as CSS pixels, i.e. 1/96 of an inch.
+    ///
+    /// Since the SVG size is rounded to whole pixels first,
+    /// the result can differ from the exact value by up to `dpi / 192` pixels.
+    Dpi(f64),
 }
 
 impl FitTo {
@@ -41,11 +51,30 @@ impl FitTo {
                 Size::new(sizef.width() * z as f64, sizef.height() * z as f64)
                     .map(|s| s.to_screen_size())
             }
+            FitTo::Dpi(dpi) => {
+                let z = dpi / 96.0;
+                Size::new(sizef.width() * z, sizef.height() * z)
+                    .map(|s| s.to_screen_size())
+            }
         }
     }
 }
 
 
+/// A missing text handling method.
+///
+/// Used when a text cannot be converted into paths, because no suitable fonts were found.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MissingText {
+    /// Skips the text.
+    Skip,
+    /// Replaces the text with a placeholder box.
+    PlaceholderBox,
+    /// Fails the conversion with `Error::MissingFonts`.
+    Error,
+}
+
+
 /// Processing options.
 #[derive(Clone, Debug)]
 pub struct Options {
@@ -115,11 +144,44 @@ pub struct Options {
     /// Default: false
     pub keep_named_groups: bool,
 
+    /// Treats unknown SVG elements as errors.
+    ///
+    /// By default, unknown SVG elements are silently skipped.
+    /// When set, parsing will fail with `Error::UnknownElements` instead.
+    /// `title`, `desc`, `metadata` and elements outside the SVG namespace are ignored.
+    ///
+    /// Only element names are checked. Unsupported attribute values,
+    /// like an unknown filter input, are still handled by a fallback,
+    /// so it doesn't guarantee that a document will be rendered faithfully.
+    ///
+    /// Default: false
+    pub reject_unknown_elements: bool,
+
+    /// Applies transforms directly to the paths geometry.
+    ///
+    /// Groups transforms are moved to their children and paths transforms
+    /// are applied to the path data, so most of the resulting tree will have no transforms.
+    /// The rendering result will not change, except for minor anti-aliasing differences,
+    /// therefore paths that cannot be transformed without affecting it keep their transform.
+    /// Like paths that use paint servers or have a stroke under a non-uniform scale or a skew.
+    /// Groups with a clip path, a mask or a filter keep their transform as well.
+    ///
+    /// Useful for debugging and paths export.
+    ///
+    /// Default: false
+    pub flatten_transforms: bool,
+
     /// When empty, `text` elements will be skipped.
     ///
     /// Default: empty
     #[cfg(feature = "text")]
     pub fontdb: fontdb::Database,
+
+    /// Specifies how a text without suitable fonts should be handled.
+    ///
+    /// Default: Skip
+    #[cfg(feature = "text")]
+    pub missing_text: MissingText,
 }
 
 impl Options {
@@ -147,8 +209,44 @@ impl Default for Options {
             text_rendering: TextRendering::default(),
             image_rendering: ImageRendering::default(),
             keep_named_groups: false,
+            reject_unknown_elements: false,
+            flatten_transforms: false,
             #[cfg(feature = "text")]
             fontdb: fontdb::Database::new(),
+            #[cfg(feature = "text")]
+            missing_text: MissingText::Skip,
         }
     }
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_to_width() {
+        let size = ScreenSize::new(200, 100).unwrap();
+        assert_eq!(FitTo::Width(1024).fit_to(size), ScreenSize::new(1024, 512));
+    }
+
+    #[test]
+    fn fit_to_width_rounds_up() {
+        let size = ScreenSize::new(300, 200).unwrap();
+        assert_eq!(FitTo::Width(100).fit_to(size), ScreenSize::new(100, 67));
+    }
+
+    #[test]
+    fn fit_to_height() {
+        let size = ScreenSize::new(200, 100).unwrap();
+        assert_eq!(FitTo::Height(512).fit_to(size), ScreenSize::new(1024, 512));
+    }
+
+    #[test]
+    fn fit_to_dpi() {
+        // 50.8mm x 25.4mm at 96 DPI.
+        let size = ScreenSize::new(192, 96).unwrap();
+        assert_eq!(FitTo::Dpi(300.0).fit_to(size), ScreenSize::new(600, 300));
+        assert_eq!(FitTo::Dpi(0.0).fit_to(size), None);
+    }
+}
@@ -0,0 +1,199 @@
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! CSS Color 4 color spaces support.
+//!
+//! `svgtypes` supports only sRGB colors, so colors in other color spaces
+//! are converted to sRGB here. Out of gamut colors are clamped.
+
+use std::str::FromStr;
+
+/// Parses a `lab()`, `lch()`, `oklab()` or `oklch()` color and converts it to sRGB.
+///
+/// The alpha component is validated, but ignored, since `svgtypes::Color` has no alpha.
+pub fn parse_css4_color(text: &str) -> Option<svgtypes::Color> {
+    let text = text.trim();
+    let start = text.find('(')?;
+    if !text.ends_with(')') {
+        return None;
+    }
+
+    let name = text[..start].trim_end().to_ascii_lowercase();
+    let args = &text[start + 1..text.len() - 1];
+
+    // The alpha is separated by a slash.
+    let args = match args.find('/') {
+        Some(idx) => {
+            let mut alpha = args[idx + 1..].split_whitespace();
+            parse_component(alpha.next()?, 1.0)?;
+            if alpha.next().is_some() {
+                return None;
+            }
+
+            &args[..idx]
+        }
+        None => args,
+    };
+
+    let mut values = args.split_whitespace();
+    let c1 = values.next()?;
+    let c2 = values.next()?;
+    let c3 = values.next()?;
+    if values.next().is_some() {
+        return None;
+    }
+
+    let (r, g, b) = match name.as_str() {
+        "lab" => {
+            let l = parse_component(c1, 100.0)?;
+            let a = parse_component(c2, 125.0)?;
+            let b = parse_component(c3, 125.0)?;
+            lab_to_linear_srgb(l, a, b)
+        }
+        "lch" => {
+            let l = parse_component(c1, 100.0)?;
+            let c = parse_component(c2, 150.0)?;
+            let (a, b) = polar_to_cartesian(c, parse_hue(c3)?);
+            lab_to_linear_srgb(l, a, b)
+        }
+        "oklab" => {
+            let l = parse_component(c1, 1.0)?;
+            let a = parse_component(c2, 0.4)?;
+            let b = parse_component(c3, 0.4)?;
+            oklab_to_linear_srgb(l, a, b)
+        }
+        "oklch" => {
+            let l = parse_component(c1, 1.0)?;
+            let c = parse_component(c2, 0.4)?;
+            let (a, b) = polar_to_cartesian(c, parse_hue(c3)?);
+            oklab_to_linear_srgb(l, a, b)
+        }
+        _ => return None,
+    };
+
+    Some(svgtypes::Color::new(to_srgb_u8(r), to_srgb_u8(g), to_srgb_u8(b)))
+}
+
+/// Parses a number or a percentage, where `100%` is equal to `percent_ref`.
+fn parse_component(text: &str, percent_ref: f64) -> Option<f64> {
+    if text.eq_ignore_ascii_case("none") {
+        return Some(0.0);
+    }
+
+    let n = if let Some(text) = text.strip_suffix('%') {
+        f64::from_str(text).ok()? / 100.0 * percent_ref
+    } else {
+        f64::from_str(text).ok()?
+    };
+
+    if n.is_finite() { Some(n) } else { None }
+}
+
+/// Parses a hue angle in degrees.
+fn parse_hue(text: &str) -> Option<f64> {
+    if text.eq_ignore_ascii_case("none") {
+        return Some(0.0);
+    }
+
+    let text = text.to_ascii_lowercase();
+    let (number, factor) = if let Some(n) = text.strip_suffix("deg") {
+        (n, 1.0)
+    } else if let Some(n) = text.strip_suffix("grad") {
+        (n, 0.9)
+    } else if let Some(n) = text.strip_suffix("rad") {
+        (n, 180.0 / std::f64::consts::PI)
+    } else if let Some(n) = text.strip_suffix("turn") {
+        (n, 360.0)
+    } else {
+        (text.as_str(), 1.0)
+    };
+
+    let n = f64::from_str(number).ok()? * factor;
+    if n.is_finite() { Some(n) } else { None }
+}
+
+fn polar_to_cartesian(chroma: f64, hue: f64) -> (f64, f64) {
+    let hue = hue.to_radians();
+    (chroma * hue.cos(), chroma * hue.sin())
+}
+
+/// Converts a CIE Lab color with a D50 white point to a linear sRGB.
+fn lab_to_linear_srgb(l: f64, a: f64, b: f64) -> (f64, f64, f64) {
+    const EPSILON: f64 = 216.0 / 24389.0;
+    const KAPPA: f64 = 24389.0 / 27.0;
+    const WHITE: [f64; 3] = [0.3457 / 0.3585, 1.0, (1.0 - 0.3457 - 0.3585) / 0.3585];
+
+    let fy = (l + 16.0) / 116.0;
+    let fx = fy + a / 500.0;
+    let fz = fy - b / 200.0;
+
+    let x = if fx.powi(3) > EPSILON { fx.powi(3) } else { (116.0 * fx - 16.0) / KAPPA };
+    let y = if l > KAPPA * EPSILON { fy.powi(3) } else { l / KAPPA };
+    let z = if fz.powi(3) > EPSILON { fz.powi(3) } else { (116.0 * fz - 16.0) / KAPPA };
+
+    let (x, y, z) = (x * WHITE[0], y * WHITE[1], z * WHITE[2]);
+
+    // Bradford chromatic adaptation from D50 to D65.
+    let (x, y, z) = (
+         0.9554734527042182 * x - 0.023098536874261423 * y + 0.0632593086610217 * z,
+        -0.028369706963208136 * x + 1.0099954580058226 * y + 0.021041398966943008 * z,
+         0.012314001688319899 * x - 0.020507696433477912 * y + 1.3303659366080753 * z,
+    );
+
+    (
+         3.2409699419045226 * x - 1.537383177570094 * y - 0.4986107602930034 * z,
+        -0.9692436362808796 * x + 1.8759675015077202 * y + 0.04155505740717559 * z,
+         0.05563007969699366 * x - 0.20397695888897652 * y + 1.0569715142428786 * z,
+    )
+}
+
+/// Converts an OKLab color to a linear sRGB.
+fn oklab_to_linear_srgb(l: f64, a: f64, b: f64) -> (f64, f64, f64) {
+    let l_ = (l + 0.3963377774 * a + 0.2158037573 * b).powi(3);
+    let m_ = (l - 0.1055613458 * a - 0.0638541728 * b).powi(3);
+    let s_ = (l - 0.0894841775 * a - 1.2914855480 * b).powi(3);
+
+    (
+         4.0767416621 * l_ - 3.3077115913 * m_ + 0.2309699292 * s_,
+        -1.2684380046 * l_ + 2.6097574011 * m_ - 0.3413193965 * s_,
+        -0.0041960863 * l_ - 0.7034186147 * m_ + 1.7076147010 * s_,
+    )
+}
+
+fn to_srgb_u8(c: f64) -> u8 {
+    let c = if c <= 0.0031308 { c * 12.92 } else { 1.055 * c.powf(1.0 / 2.4) - 0.055 };
+    (c.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test {
+        ($name:ident, $text:expr, $r:expr, $g:expr, $b:expr) => {
+            #[test]
+            fn $name() {
+                let c = parse_css4_color($text).unwrap();
+                assert_eq!((c.red, c.green, c.blue), ($r, $g, $b));
+            }
+        };
+    }
+
+    test!(lab_white, "lab(100% 0 0)", 255, 255, 255);
+    test!(lab_red, "lab(54.29 80.8 69.89)", 255, 0, 0);
+    test!(lch_red, "lch(54.29% 106.84 40.86deg / 0.5)", 255, 0, 0);
+    test!(oklab_black, "oklab(0 none none)", 0, 0, 0);
+    test!(oklch_red, "oklch(62.8% 0.2577 29.23)", 255, 0, 0);
+    test!(oklch_out_of_gamut, "oklch(0.9 0.4 145)", 0, 255, 0);
+
+    #[test]
+    fn unsupported() {
+        assert!(parse_css4_color("hwb(0 0% 0%)").is_none());
+        assert!(parse_css4_color("lab(50% 0)").is_none());
+        assert!(parse_css4_color("lab(50% 0 0 0)").is_none());
+        assert!(parse_css4_color("lab(50% 0 0 / )").is_none());
+        assert!(parse_css4_color("lab(50% 0 0 / x)").is_none());
+        assert!(parse_css4_color("lab(50% 0 0 / 0.5 0.5)").is_none());
+    }
+}
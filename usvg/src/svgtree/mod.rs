@@ -12,6 +12,8 @@ use svgtypes::FuzzyEq;
 use crate::geom::Rect;
 use crate::tree;
 
+mod color;
+
 mod parse;
 pub use parse::*;
 
@@ -25,6 +27,7 @@ pub struct Document {
     nodes: Vec<NodeData>,
     attrs: Vec<Attribute>,
     links: HashMap<String, NodeId>,
+    unknown: Vec<String>,
 }
 
 impl Document {
@@ -52,6 +55,14 @@ impl Document {
     pub fn get(&self, id: NodeId) -> Node {
         Node { id, d: &self.nodes[id.0], doc: self }
     }
+
+    /// Returns a sorted list of unique SVG elements names that were skipped during parsing.
+    pub fn unknown_elements(&self) -> Vec<String> {
+        let mut names = self.unknown.clone();
+        names.sort();
+        names.dedup();
+        names
+    }
 }
 
 impl fmt::Debug for Document {
@@ -764,6 +775,8 @@ impl AId {
             | AId::Mask
             | AId::Opacity
             | AId::Overflow
+            | AId::PaintOrder
+            | AId::PointerEvents
             | AId::ShapeRendering
             | AId::StopColor
             | AId::StopOpacity
@@ -778,6 +791,7 @@ impl AId {
             | AId::TextAnchor
             | AId::TextDecoration
             | AId::TextRendering
+            | AId::VectorEffect
             | AId::Visibility
             | AId::WordSpacing
             | AId::WritingMode)
@@ -820,6 +834,8 @@ impl AId {
             | AId::Mask
             | AId::Opacity
             | AId::Overflow
+            | AId::PaintOrder
+            | AId::PointerEvents
             | AId::ShapeRendering
             | AId::StopColor
             | AId::StopOpacity
@@ -834,6 +850,7 @@ impl AId {
             | AId::TextAnchor
             | AId::TextDecoration
             | AId::TextRendering
+            | AId::VectorEffect
             | AId::Visibility
             | AId::WordSpacing
             | AId::WritingMode)
@@ -853,5 +870,6 @@ fn is_non_inheritable(id: AId) -> bool {
         | AId::Overflow
         | AId::StopColor
         | AId::StopOpacity
-        | AId::TextDecoration)
+        | AId::TextDecoration
+        | AId::VectorEffect)
 }
@@ -18,6 +18,7 @@ pub enum EId {
     FeDiffuseLighting,
     FeDisplacementMap,
     FeDistantLight,
+    FeDropShadow,
     FeFlood,
     FeFuncA,
     FeFuncB,
@@ -62,71 +63,72 @@ pub enum EId {
 static ELEMENTS: Map<EId> = Map {
     key: 3558916427560184125,
     disps: &[
-        (1, 13),
-        (5, 3),
-        (0, 19),
-        (6, 24),
+        (8, 3),
+        (1, 42),
+        (1, 10),
+        (30, 41),
         (0, 0),
-        (0, 3),
+        (2, 10),
+        (0, 4),
+        (42, 15),
         (0, 1),
-        (11, 10),
-        (0, 21),
         (0, 0),
-        (5, 0),
+        (22, 1),
     ],
     entries: &[
-        ("feConvolveMatrix", EId::FeConvolveMatrix),
-        ("tspan", EId::Tspan),
-        ("style", EId::Style),
-        ("feFuncB", EId::FeFuncB),
-        ("rect", EId::Rect),
-        ("marker", EId::Marker),
-        ("feDiffuseLighting", EId::FeDiffuseLighting),
-        ("g", EId::G),
-        ("symbol", EId::Symbol),
-        ("pattern", EId::Pattern),
+        ("tref", EId::Tref),
         ("path", EId::Path),
-        ("feFuncR", EId::FeFuncR),
-        ("a", EId::A),
-        ("textPath", EId::TextPath),
-        ("use", EId::Use),
+        ("pattern", EId::Pattern),
+        ("feConvolveMatrix", EId::FeConvolveMatrix),
+        ("text", EId::Text),
+        ("defs", EId::Defs),
+        ("svg", EId::Svg),
+        ("feDropShadow", EId::FeDropShadow),
+        ("feMergeNode", EId::FeMergeNode),
+        ("polygon", EId::Polygon),
+        ("linearGradient", EId::LinearGradient),
         ("feFuncA", EId::FeFuncA),
-        ("tref", EId::Tref),
         ("circle", EId::Circle),
-        ("fePointLight", EId::FePointLight),
-        ("defs", EId::Defs),
+        ("ellipse", EId::Ellipse),
+        ("a", EId::A),
+        ("radialGradient", EId::RadialGradient),
+        ("mask", EId::Mask),
         ("feTile", EId::FeTile),
-        ("image", EId::Image),
-        ("stop", EId::Stop),
-        ("feGaussianBlur", EId::FeGaussianBlur),
-        ("feFlood", EId::FeFlood),
-        ("polyline", EId::Polyline),
+        ("filter", EId::Filter),
         ("feComponentTransfer", EId::FeComponentTransfer),
-        ("linearGradient", EId::LinearGradient),
+        ("switch", EId::Switch),
+        ("feImage", EId::FeImage),
+        ("feDisplacementMap", EId::FeDisplacementMap),
+        ("g", EId::G),
+        ("style", EId::Style),
+        ("line", EId::Line),
+        ("feFlood", EId::FeFlood),
+        ("tspan", EId::Tspan),
         ("feFuncG", EId::FeFuncG),
-        ("ellipse", EId::Ellipse),
-        ("clipPath", EId::ClipPath),
-        ("feMerge", EId::FeMerge),
+        ("feOffset", EId::FeOffset),
         ("feSpotLight", EId::FeSpotLight),
+        ("polyline", EId::Polyline),
+        ("feDistantLight", EId::FeDistantLight),
         ("feBlend", EId::FeBlend),
-        ("svg", EId::Svg),
-        ("feColorMatrix", EId::FeColorMatrix),
-        ("feTurbulence", EId::FeTurbulence),
+        ("feFuncB", EId::FeFuncB),
         ("feSpecularLighting", EId::FeSpecularLighting),
-        ("switch", EId::Switch),
+        ("feDiffuseLighting", EId::FeDiffuseLighting),
+        ("marker", EId::Marker),
+        ("feMerge", EId::FeMerge),
+        ("feTurbulence", EId::FeTurbulence),
+        ("stop", EId::Stop),
+        ("rect", EId::Rect),
+        ("clipPath", EId::ClipPath),
         ("feMorphology", EId::FeMorphology),
-        ("feImage", EId::FeImage),
-        ("feMergeNode", EId::FeMergeNode),
-        ("feOffset", EId::FeOffset),
-        ("polygon", EId::Polygon),
+        ("feFuncR", EId::FeFuncR),
+        ("feColorMatrix", EId::FeColorMatrix),
+        ("feGaussianBlur", EId::FeGaussianBlur),
+        ("use", EId::Use),
         ("feComposite", EId::FeComposite),
-        ("radialGradient", EId::RadialGradient),
-        ("line", EId::Line),
-        ("feDisplacementMap", EId::FeDisplacementMap),
-        ("feDistantLight", EId::FeDistantLight),
-        ("mask", EId::Mask),
-        ("text", EId::Text),
-        ("filter", EId::Filter),
+        ("fePointLight", EId::FePointLight),
+        ("symbol", EId::Symbol),
+        ("image", EId::Image),
+        ("textPath", EId::TextPath),
     ],
 };
 
@@ -229,9 +231,11 @@ pub enum AId {
     Order,
     Orient,
     Overflow,
+    PaintOrder,
     PatternContentUnits,
     PatternTransform,
     PatternUnits,
+    PointerEvents,
     Points,
     PointsAtX,
     PointsAtY,
@@ -280,8 +284,10 @@ pub enum AId {
     TextDecoration,
     TextRendering,
     Transform,
+    TransformOrigin,
     Type,
     Values,
+    VectorEffect,
     ViewBox,
     Visibility,
     Width,
@@ -299,180 +305,184 @@ pub enum AId {
 }
 
 static ATTRIBUTES: Map<AId> = Map {
-    key: 3213172566270843353,
+    key: 1164115433906158532,
     disps: &[
-        (0, 32),
+        (0, 88),
+        (0, 57),
+        (0, 91),
+        (0, 4),
+        (1, 129),
+        (2, 5),
+        (0, 7),
+        (1, 81),
+        (0, 28),
+        (1, 108),
+        (1, 69),
         (0, 1),
-        (0, 16),
-        (0, 31),
-        (9, 125),
-        (1, 4),
-        (5, 102),
-        (0, 44),
-        (11, 69),
-        (5, 77),
-        (0, 20),
-        (0, 8),
-        (0, 3),
-        (0, 8),
-        (2, 43),
-        (1, 0),
-        (0, 0),
-        (25, 133),
-        (12, 99),
-        (0, 3),
+        (0, 10),
         (0, 68),
-        (0, 33),
-        (1, 54),
-        (0, 64),
-        (2, 119),
-        (0, 18),
-        (0, 7),
-        (1, 19),
-        (9, 46),
+        (0, 131),
+        (0, 6),
+        (4, 137),
+        (0, 0),
+        (7, 88),
+        (5, 113),
+        (68, 106),
+        (0, 10),
+        (9, 142),
+        (0, 0),
+        (3, 23),
+        (14, 14),
+        (5, 86),
+        (0, 13),
+        (53, 9),
     ],
     entries: &[
-        ("pointsAtZ", AId::PointsAtZ),
-        ("stop-color", AId::StopColor),
-        ("xChannelSelector", AId::XChannelSelector),
+        ("stroke-dashoffset", AId::StrokeDashoffset),
+        ("gradientTransform", AId::GradientTransform),
+        ("x1", AId::X1),
         ("systemLanguage", AId::SystemLanguage),
-        ("cy", AId::Cy),
-        ("dy", AId::Dy),
-        ("preserveAlpha", AId::PreserveAlpha),
-        ("preserveAspectRatio", AId::PreserveAspectRatio),
+        ("viewBox", AId::ViewBox),
+        ("fill-opacity", AId::FillOpacity),
+        ("exponent", AId::Exponent),
+        ("specularConstant", AId::SpecularConstant),
+        ("seed", AId::Seed),
+        ("width", AId::Width),
+        ("elevation", AId::Elevation),
+        ("text-rendering", AId::TextRendering),
+        ("limitingConeAngle", AId::LimitingConeAngle),
+        ("maskUnits", AId::MaskUnits),
         ("lighting-color", AId::LightingColor),
-        ("stroke-dashoffset", AId::StrokeDashoffset),
-        ("word-spacing", AId::WordSpacing),
-        ("font-style", AId::FontStyle),
-        ("edgeMode", AId::EdgeMode),
-        ("pointsAtY", AId::PointsAtY),
-        ("id", AId::Id),
-        ("type", AId::Type),
-        ("targetX", AId::TargetX),
-        ("fill", AId::Fill),
+        ("order", AId::Order),
         ("k3", AId::K3),
-        ("kernelUnitLength", AId::KernelUnitLength),
-        ("viewBox", AId::ViewBox),
-        ("baseFrequency", AId::BaseFrequency),
-        ("stroke", AId::Stroke),
-        ("divisor", AId::Divisor),
+        ("font-stretch", AId::FontStretch),
+        ("dy", AId::Dy),
         ("slope", AId::Slope),
+        ("maskContentUnits", AId::MaskContentUnits),
+        ("y1", AId::Y1),
+        ("rotate", AId::Rotate),
+        ("pointsAtY", AId::PointsAtY),
+        ("stdDeviation", AId::StdDeviation),
+        ("cy", AId::Cy),
+        ("direction", AId::Direction),
+        ("flood-opacity", AId::FloodOpacity),
+        ("y2", AId::Y2),
+        ("baseline-shift", AId::BaselineShift),
         ("markerUnits", AId::MarkerUnits),
-        ("d", AId::D),
+        ("r", AId::R),
+        ("rx", AId::Rx),
+        ("refX", AId::RefX),
+        ("clip-path", AId::ClipPath),
+        ("intercept", AId::Intercept),
+        ("xChannelSelector", AId::XChannelSelector),
+        ("stop-color", AId::StopColor),
+        ("fx", AId::Fx),
+        ("x", AId::X),
+        ("display", AId::Display),
+        ("in", AId::In),
+        ("transform-origin", AId::TransformOrigin),
+        ("startOffset", AId::StartOffset),
+        ("in2", AId::In2),
+        ("vector-effect", AId::VectorEffect),
+        ("clip-rule", AId::ClipRule),
         ("clipPathUnits", AId::ClipPathUnits),
-        ("stop-opacity", AId::StopOpacity),
-        ("transform", AId::Transform),
-        ("stroke-dasharray", AId::StrokeDasharray),
-        ("dx", AId::Dx),
-        ("specularExponent", AId::SpecularExponent),
-        ("z", AId::Z),
-        ("direction", AId::Direction),
-        ("letter-spacing", AId::LetterSpacing),
-        ("stroke-miterlimit", AId::StrokeMiterlimit),
+        ("result", AId::Result),
+        ("requiredFeatures", AId::RequiredFeatures),
+        ("targetY", AId::TargetY),
         ("overflow", AId::Overflow),
-        ("order", AId::Order),
-        ("intercept", AId::Intercept),
-        ("operator", AId::Operator),
-        ("font-variant", AId::FontVariant),
-        ("filterUnits", AId::FilterUnits),
-        ("values", AId::Values),
-        ("filter", AId::Filter),
-        ("rotate", AId::Rotate),
-        ("href", AId::Href),
-        ("requiredExtensions", AId::RequiredExtensions),
-        ("font-stretch", AId::FontStretch),
-        ("clip-path", AId::ClipPath),
-        ("x1", AId::X1),
-        ("pointsAtX", AId::PointsAtX),
-        ("k4", AId::K4),
-        ("font-weight", AId::FontWeight),
-        ("class", AId::Class),
-        ("patternTransform", AId::PatternTransform),
+        ("text-decoration", AId::TextDecoration),
         ("markerHeight", AId::MarkerHeight),
-        ("writing-mode", AId::WritingMode),
-        ("clip-rule", AId::ClipRule),
-        ("y1", AId::Y1),
-        ("patternContentUnits", AId::PatternContentUnits),
-        ("primitiveUnits", AId::PrimitiveUnits),
-        ("x2", AId::X2),
-        ("y", AId::Y),
-        ("marker-mid", AId::MarkerMid),
+        ("color-interpolation-filters", AId::ColorInterpolationFilters),
+        ("image-rendering", AId::ImageRendering),
+        ("numOctaves", AId::NumOctaves),
+        ("fill-rule", AId::FillRule),
+        ("word-spacing", AId::WordSpacing),
+        ("stroke-linecap", AId::StrokeLinecap),
         ("offset", AId::Offset),
-        ("stroke-width", AId::StrokeWidth),
-        ("markerWidth", AId::MarkerWidth),
-        ("fill-opacity", AId::FillOpacity),
-        ("refX", AId::RefX),
-        ("maskContentUnits", AId::MaskContentUnits),
+        ("diffuseConstant", AId::DiffuseConstant),
+        ("k1", AId::K1),
         ("shape-rendering", AId::ShapeRendering),
+        ("stroke-dasharray", AId::StrokeDasharray),
+        ("space", AId::Space),
         ("amplitude", AId::Amplitude),
-        ("stitchTiles", AId::StitchTiles),
-        ("maskUnits", AId::MaskUnits),
-        ("fx", AId::Fx),
-        ("color", AId::Color),
-        ("numOctaves", AId::NumOctaves),
-        ("stdDeviation", AId::StdDeviation),
-        ("y2", AId::Y2),
-        ("mask", AId::Mask),
-        ("spreadMethod", AId::SpreadMethod),
-        ("display", AId::Display),
-        ("opacity", AId::Opacity),
-        ("font-size", AId::FontSize),
-        ("stroke-linejoin", AId::StrokeLinejoin),
-        ("image-rendering", AId::ImageRendering),
+        ("values", AId::Values),
         ("azimuth", AId::Azimuth),
-        ("in", AId::In),
-        ("ry", AId::Ry),
+        ("surfaceScale", AId::SurfaceScale),
+        ("font-variant", AId::FontVariant),
+        ("stroke-width", AId::StrokeWidth),
+        ("mode", AId::Mode),
+        ("x2", AId::X2),
+        ("font-size", AId::FontSize),
+        ("class", AId::Class),
+        ("height", AId::Height),
+        ("preserveAlpha", AId::PreserveAlpha),
         ("bias", AId::Bias),
-        ("radius", AId::Radius),
-        ("enable-background", AId::EnableBackground),
-        ("gradientUnits", AId::GradientUnits),
-        ("specularConstant", AId::SpecularConstant),
-        ("limitingConeAngle", AId::LimitingConeAngle),
-        ("flood-color", AId::FloodColor),
-        ("scale", AId::Scale),
-        ("x", AId::X),
-        ("diffuseConstant", AId::DiffuseConstant),
-        ("refY", AId::RefY),
-        ("yChannelSelector", AId::YChannelSelector),
+        ("pointer-events", AId::PointerEvents),
+        ("points", AId::Points),
+        ("kernelUnitLength", AId::KernelUnitLength),
+        ("filter", AId::Filter),
+        ("operator", AId::Operator),
+        ("stitchTiles", AId::StitchTiles),
         ("style", AId::Style),
-        ("gradientTransform", AId::GradientTransform),
-        ("fill-rule", AId::FillRule),
-        ("height", AId::Height),
-        ("kernelMatrix", AId::KernelMatrix),
-        ("text-anchor", AId::TextAnchor),
-        ("stroke-linecap", AId::StrokeLinecap),
-        ("text-decoration", AId::TextDecoration),
-        ("rx", AId::Rx),
-        ("requiredFeatures", AId::RequiredFeatures),
-        ("patternUnits", AId::PatternUnits),
-        ("mode", AId::Mode),
-        ("marker-start", AId::MarkerStart),
-        ("visibility", AId::Visibility),
-        ("result", AId::Result),
-        ("seed", AId::Seed),
-        ("in2", AId::In2),
+        ("requiredExtensions", AId::RequiredExtensions),
+        ("targetX", AId::TargetX),
+        ("dx", AId::Dx),
+        ("font-weight", AId::FontWeight),
+        ("stroke-opacity", AId::StrokeOpacity),
+        ("type", AId::Type),
+        ("enable-background", AId::EnableBackground),
+        ("tableValues", AId::TableValues),
+        ("orient", AId::Orient),
         ("cx", AId::Cx),
+        ("patternContentUnits", AId::PatternContentUnits),
         ("k2", AId::K2),
-        ("flood-opacity", AId::FloodOpacity),
-        ("k1", AId::K1),
-        ("text-rendering", AId::TextRendering),
-        ("r", AId::R),
+        ("patternUnits", AId::PatternUnits),
+        ("primitiveUnits", AId::PrimitiveUnits),
+        ("preserveAspectRatio", AId::PreserveAspectRatio),
+        ("baseFrequency", AId::BaseFrequency),
+        ("transform", AId::Transform),
+        ("marker-mid", AId::MarkerMid),
+        ("gradientUnits", AId::GradientUnits),
+        ("paint-order", AId::PaintOrder),
+        ("flood-color", AId::FloodColor),
         ("marker-end", AId::MarkerEnd),
-        ("surfaceScale", AId::SurfaceScale),
-        ("tableValues", AId::TableValues),
-        ("orient", AId::Orient),
-        ("color-interpolation-filters", AId::ColorInterpolationFilters),
-        ("points", AId::Points),
-        ("baseline-shift", AId::BaselineShift),
-        ("elevation", AId::Elevation),
-        ("width", AId::Width),
         ("font-family", AId::FontFamily),
-        ("space", AId::Space),
-        ("startOffset", AId::StartOffset),
+        ("pointsAtZ", AId::PointsAtZ),
+        ("yChannelSelector", AId::YChannelSelector),
+        ("patternTransform", AId::PatternTransform),
+        ("letter-spacing", AId::LetterSpacing),
+        ("stroke-linejoin", AId::StrokeLinejoin),
+        ("id", AId::Id),
+        ("stroke-miterlimit", AId::StrokeMiterlimit),
+        ("visibility", AId::Visibility),
+        ("font-style", AId::FontStyle),
+        ("d", AId::D),
+        ("scale", AId::Scale),
+        ("z", AId::Z),
+        ("text-anchor", AId::TextAnchor),
+        ("color", AId::Color),
+        ("radius", AId::Radius),
+        ("pointsAtX", AId::PointsAtX),
+        ("href", AId::Href),
+        ("k4", AId::K4),
+        ("fill", AId::Fill),
+        ("stop-opacity", AId::StopOpacity),
+        ("divisor", AId::Divisor),
         ("fy", AId::Fy),
-        ("stroke-opacity", AId::StrokeOpacity),
-        ("targetY", AId::TargetY),
-        ("exponent", AId::Exponent),
+        ("writing-mode", AId::WritingMode),
+        ("refY", AId::RefY),
+        ("filterUnits", AId::FilterUnits),
+        ("spreadMethod", AId::SpreadMethod),
+        ("ry", AId::Ry),
+        ("marker-start", AId::MarkerStart),
+        ("kernelMatrix", AId::KernelMatrix),
+        ("y", AId::Y),
+        ("specularExponent", AId::SpecularExponent),
+        ("mask", AId::Mask),
+        ("markerWidth", AId::MarkerWidth),
+        ("stroke", AId::Stroke),
+        ("opacity", AId::Opacity),
+        ("edgeMode", AId::EdgeMode),
     ],
 };
 
@@ -76,6 +76,7 @@ fn parse(text: &str) -> Result<Document, Error> {
         nodes: Vec::new(),
         attrs: Vec::new(),
         links: HashMap::new(),
+        unknown: Vec::new(),
     };
 
     // Add a root node.
@@ -130,6 +131,17 @@ fn parse_tag_name(node: roxmltree::Node) -> Option<EId> {
     EId::from_str(node.tag_name().name())
 }
 
+/// Checks that the node is an SVG element that will be ignored.
+///
+/// Descriptive elements are not rendered by design, so they are not reported.
+fn is_unknown_element(node: roxmltree::Node) -> bool {
+    if !node.is_element() || node.tag_name().namespace() != Some(SVG_NS) {
+        return false;
+    }
+
+    !matches!(node.tag_name().name(), "title" | "desc" | "metadata")
+}
+
 fn parse_xml_node_children(
     parent: roxmltree::Node,
     origin: roxmltree::Node,
@@ -153,7 +165,13 @@ fn parse_xml_node(
 ) {
     let mut tag_name = match parse_tag_name(node) {
         Some(id) => id,
-        None => return,
+        None => {
+            if is_unknown_element(node) {
+                doc.unknown.push(node.tag_name().name().to_string());
+            }
+
+            return;
+        }
     };
 
     if tag_name == EId::Style {
@@ -233,7 +251,7 @@ fn parse_svg_element(
                 if let Some(aid) = AId::from_str(declaration.name) {
                     // Parse only the presentation attributes.
                     // `transform` isn't a presentation attribute, but should be parsed anyway.
-                    if aid.is_presentation() || aid == AId::Transform {
+                    if aid.is_presentation() || aid == AId::Transform || aid == AId::TransformOrigin {
                         insert_attribute(aid, declaration.value);
                     }
                 } else if declaration.name == "marker" {
@@ -252,7 +270,7 @@ fn parse_svg_element(
             if let Some(aid) = AId::from_str(declaration.name) {
                 // Parse only the presentation attributes.
                 // `transform` isn't a presentation attribute, but should be parsed anyway.
-                if aid.is_presentation() || aid == AId::Transform {
+                if aid.is_presentation() || aid == AId::Transform || aid == AId::TransformOrigin {
                     insert_attribute(aid, declaration.value);
                 }
             }
@@ -418,21 +436,27 @@ fn parse_svg_attribute(
                     AttributeValue::Paint(link.to_string(), fallback)
                 }
                 Err(_) => {
-                    warn!("Failed to parse fill value: '{}'. Fallback to black.", value);
-                    AttributeValue::Color(svgtypes::Color::black())
+                    match super::color::parse_css4_color(value) {
+                        Some(color) => AttributeValue::Color(color),
+                        None => {
+                            warn!("Failed to parse fill value: '{}'. Fallback to black.", value);
+                            AttributeValue::Color(svgtypes::Color::black())
+                        }
+                    }
                 }
             }
         }
 
         AId::Stroke => {
-            match svgtypes::Paint::from_str(value)? {
-                svgtypes::Paint::None => AttributeValue::None,
-                svgtypes::Paint::Inherit => unreachable!(),
-                svgtypes::Paint::CurrentColor => AttributeValue::CurrentColor,
-                svgtypes::Paint::Color(color) => AttributeValue::Color(color),
-                svgtypes::Paint::FuncIRI(link, fallback) => {
+            match svgtypes::Paint::from_str(value) {
+                Ok(svgtypes::Paint::None) => AttributeValue::None,
+                Ok(svgtypes::Paint::Inherit) => unreachable!(),
+                Ok(svgtypes::Paint::CurrentColor) => AttributeValue::CurrentColor,
+                Ok(svgtypes::Paint::Color(color)) => AttributeValue::Color(color),
+                Ok(svgtypes::Paint::FuncIRI(link, fallback)) => {
                     AttributeValue::Paint(link.to_string(), fallback)
                 }
+                Err(e) => AttributeValue::Color(super::color::parse_css4_color(value).ok_or(e)?),
             }
         }
 
@@ -453,7 +477,7 @@ fn parse_svg_attribute(
         }
 
         AId::Color => {
-            AttributeValue::Color(svgtypes::Color::from_str(value)?)
+            AttributeValue::Color(parse_color(value)?)
         }
 
           AId::FloodColor
@@ -461,7 +485,7 @@ fn parse_svg_attribute(
         | AId::StopColor => {
             match value {
                 "currentColor" => AttributeValue::CurrentColor,
-                _ => AttributeValue::Color(svgtypes::Color::from_str(value)?),
+                _ => AttributeValue::Color(parse_color(value)?),
             }
         }
 
@@ -543,6 +567,14 @@ fn parse_svg_attribute(
     })
 }
 
+/// Parses a color, falling back to CSS Color 4 color spaces.
+fn parse_color(value: &str) -> Result<svgtypes::Color, svgtypes::Error> {
+    match svgtypes::Color::from_str(value) {
+        Ok(color) => Ok(color),
+        Err(e) => super::color::parse_css4_color(value).ok_or(e),
+    }
+}
+
 #[inline(never)]
 fn parse_number(value: &str) -> Result<f64, svgtypes::Error> {
     let mut s = svgtypes::Stream::from(value);
@@ -834,13 +866,15 @@ fn resolve_inherit(
         | AId::Mask
         | AId::Stroke
         | AId::StrokeDasharray
-        | AId::TextDecoration => "none",
+        | AId::TextDecoration
+        | AId::VectorEffect => "none",
 
           AId::FontStretch
         | AId::FontStyle
         | AId::FontVariant
         | AId::FontWeight
         | AId::LetterSpacing
+        | AId::PaintOrder
         | AId::WordSpacing => "normal",
 
           AId::Fill
@@ -861,6 +895,7 @@ fn resolve_inherit(
         AId::Direction =>                   "ltr",
         AId::Display =>                     "inline",
         AId::FontSize =>                    "medium",
+        AId::PointerEvents =>               "visiblePainted",
         AId::StrokeDashoffset =>            "0",
         AId::StrokeLinecap =>               "butt",
         AId::StrokeLinejoin =>              "miter",
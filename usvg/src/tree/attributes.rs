@@ -57,16 +57,22 @@ impl_enum_from_str!(LineCap,
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub enum LineJoin {
     Miter,
+    /// SVG 2 `miter-clip`.
+    MiterClip,
     Round,
     Bevel,
+    /// SVG 2 `arcs`.
+    Arcs,
 }
 
 impl_enum_default!(LineJoin, Miter);
 
 impl_enum_from_str!(LineJoin,
     "miter" => LineJoin::Miter,
+    "miter-clip" => LineJoin::MiterClip,
     "round" => LineJoin::Round,
-    "bevel" => LineJoin::Bevel
+    "bevel" => LineJoin::Bevel,
+    "arcs" => LineJoin::Arcs
 );
 
 
@@ -313,6 +319,17 @@ pub enum FeBlendMode {
     Screen,
     Darken,
     Lighten,
+    Overlay,
+    ColorDodge,
+    ColorBurn,
+    HardLight,
+    SoftLight,
+    Difference,
+    Exclusion,
+    Hue,
+    Saturation,
+    Color,
+    Luminosity,
 }
 
 
@@ -495,6 +512,98 @@ impl_enum_from_str!(ShapeRendering,
 impl_from_str!(ShapeRendering);
 
 
+/// A pointer events mode.
+///
+/// `pointer-events` attribute in the SVG.
+///
+/// Doesn't affect rendering and used only by hit-testing.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[allow(missing_docs)]
+pub enum PointerEvents {
+    VisiblePainted,
+    VisibleFill,
+    VisibleStroke,
+    Visible,
+    Painted,
+    Fill,
+    Stroke,
+    All,
+    None,
+    BoundingBox,
+}
+
+impl PointerEvents {
+    /// Checks if only visible elements can be hit.
+    pub fn requires_visibility(self) -> bool {
+        matches!(self,
+              PointerEvents::VisiblePainted
+            | PointerEvents::VisibleFill
+            | PointerEvents::VisibleStroke
+            | PointerEvents::Visible)
+    }
+
+    /// Checks if only painted parts of an element can be hit.
+    pub fn requires_paint(self) -> bool {
+        matches!(self, PointerEvents::VisiblePainted | PointerEvents::Painted)
+    }
+}
+
+impl_enum_default!(PointerEvents, VisiblePainted);
+
+impl_enum_from_str!(PointerEvents,
+    "visiblePainted"    => PointerEvents::VisiblePainted,
+    "visibleFill"       => PointerEvents::VisibleFill,
+    "visibleStroke"     => PointerEvents::VisibleStroke,
+    "visible"           => PointerEvents::Visible,
+    "painted"           => PointerEvents::Painted,
+    "fill"              => PointerEvents::Fill,
+    "stroke"            => PointerEvents::Stroke,
+    "all"               => PointerEvents::All,
+    "none"              => PointerEvents::None,
+    "bounding-box"      => PointerEvents::BoundingBox
+);
+
+impl_from_str!(PointerEvents);
+
+
+/// A vector effect.
+///
+/// `vector-effect` attribute in the SVG.
+///
+/// Only `non-scaling-size` is supported. Other values are parsed as `none`.
+/// Markers are converted into separate paths and are not affected by the path `vector-effect`.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum VectorEffect {
+    None,
+    NonScalingSize,
+}
+
+impl_enum_default!(VectorEffect, None);
+
+impl_enum_from_str!(VectorEffect,
+    "none"              => VectorEffect::None,
+    "non-scaling-size"  => VectorEffect::NonScalingSize
+);
+
+impl_from_str!(VectorEffect);
+
+
+/// A path's paint order.
+///
+/// `paint-order` attribute in the SVG.
+///
+/// Markers are not a part of it, since they are stored as separate nodes.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PaintOrder {
+    FillAndStroke,
+    StrokeAndFill,
+}
+
+impl_enum_default!(PaintOrder, FillAndStroke);
+
+
 /// A text rendering method.
 ///
 /// `text-rendering` attribute in the SVG.
@@ -14,14 +14,18 @@ use crate::{geom::*, svgtree::{EId, AId}, IsDefault};
 
 
 pub fn convert(tree: &Tree, opt: XmlOptions) -> String {
-    let mut xml = XmlWriter::new(opt);
-
     let svg_node = tree.svg_node();
+    convert_with_view_box(tree, svg_node.size, svg_node.view_box, opt)
+}
+
+/// Converts an SVG using a custom size and view box.
+pub fn convert_with_view_box(tree: &Tree, size: Size, view_box: ViewBox, opt: XmlOptions) -> String {
+    let mut xml = XmlWriter::new(opt);
 
     xml.start_svg_element(EId::Svg);
-    xml.write_svg_attribute(AId::Width, &svg_node.size.width());
-    xml.write_svg_attribute(AId::Height, &svg_node.size.height());
-    xml.write_viewbox(&svg_node.view_box);
+    xml.write_svg_attribute(AId::Width, &size.width());
+    xml.write_svg_attribute(AId::Height, &size.height());
+    xml.write_viewbox(&view_box);
     xml.write_attribute("xmlns", "http://www.w3.org/2000/svg");
     if has_xlink(tree) {
         xml.write_attribute("xmlns:xlink", "http://www.w3.org/1999/xlink");
@@ -150,10 +154,36 @@ fn conv_defs(
                                 FeBlendMode::Screen     => "screen",
                                 FeBlendMode::Darken     => "darken",
                                 FeBlendMode::Lighten    => "lighten",
+                                FeBlendMode::Overlay    => "overlay",
+                                FeBlendMode::ColorDodge => "color-dodge",
+                                FeBlendMode::ColorBurn  => "color-burn",
+                                FeBlendMode::HardLight  => "hard-light",
+                                FeBlendMode::SoftLight  => "soft-light",
+                                FeBlendMode::Difference => "difference",
+                                FeBlendMode::Exclusion  => "exclusion",
+                                FeBlendMode::Hue        => "hue",
+                                FeBlendMode::Saturation => "saturation",
+                                FeBlendMode::Color      => "color",
+                                FeBlendMode::Luminosity => "luminosity",
                             });
                             xml.write_svg_attribute(AId::Result, &fe.result);
                             xml.end_element();
                         }
+                        FilterKind::FeDropShadow(ref shadow) => {
+                            xml.start_svg_element(EId::FeDropShadow);
+                            xml.write_filter_primitive_attrs(fe);
+                            xml.write_filter_input(AId::In, &shadow.input);
+                            xml.write_svg_attribute(AId::Dx, &shadow.dx);
+                            xml.write_svg_attribute(AId::Dy, &shadow.dy);
+                            xml.write_attribute_fmt(
+                                AId::StdDeviation.to_str(),
+                                format_args!("{} {}", shadow.std_dev_x.value(), shadow.std_dev_y.value()),
+                            );
+                            xml.write_svg_attribute(AId::FloodColor, &shadow.color);
+                            xml.write_svg_attribute(AId::FloodOpacity, &shadow.opacity.value());
+                            xml.write_svg_attribute(AId::Result, &fe.result);
+                            xml.end_element();
+                        }
                         FilterKind::FeFlood(ref flood) => {
                             xml.start_svg_element(EId::FeFlood);
                             xml.write_filter_primitive_attrs(fe);
@@ -759,6 +789,27 @@ fn write_path(
         ShapeRendering::GeometricPrecision => {}
     }
 
+    if path.vector_effect == VectorEffect::NonScalingSize {
+        xml.write_svg_attribute(AId::VectorEffect, "non-scaling-size");
+    }
+
+    if path.paint_order == PaintOrder::StrokeAndFill {
+        xml.write_svg_attribute(AId::PaintOrder, "stroke");
+    }
+
+    match path.pointer_events {
+        PointerEvents::VisiblePainted => {}
+        PointerEvents::VisibleFill => xml.write_svg_attribute(AId::PointerEvents, "visibleFill"),
+        PointerEvents::VisibleStroke => xml.write_svg_attribute(AId::PointerEvents, "visibleStroke"),
+        PointerEvents::Visible => xml.write_svg_attribute(AId::PointerEvents, "visible"),
+        PointerEvents::Painted => xml.write_svg_attribute(AId::PointerEvents, "painted"),
+        PointerEvents::Fill => xml.write_svg_attribute(AId::PointerEvents, "fill"),
+        PointerEvents::Stroke => xml.write_svg_attribute(AId::PointerEvents, "stroke"),
+        PointerEvents::All => xml.write_svg_attribute(AId::PointerEvents, "all"),
+        PointerEvents::None => xml.write_svg_attribute(AId::PointerEvents, "none"),
+        PointerEvents::BoundingBox => xml.write_svg_attribute(AId::PointerEvents, "bounding-box"),
+    }
+
     if let Some(ref id) = clip_path {
         xml.write_func_iri(AId::ClipPath, id);
     }
@@ -868,8 +919,10 @@ fn write_stroke(
 
         match stroke.linejoin {
             LineJoin::Miter => {}
+            LineJoin::MiterClip => xml.write_svg_attribute(AId::StrokeLinejoin, "miter-clip"),
             LineJoin::Round => xml.write_svg_attribute(AId::StrokeLinejoin, "round"),
             LineJoin::Bevel => xml.write_svg_attribute(AId::StrokeLinejoin, "bevel"),
+            LineJoin::Arcs => xml.write_svg_attribute(AId::StrokeLinejoin, "arcs"),
         }
 
         if let Some(ref array) = stroke.dasharray {
@@ -8,7 +8,7 @@ use std::cell::Ref;
 use std::path;
 
 pub use self::{nodes::*, attributes::*, pathdata::*};
-use crate::{svgtree, Rect, Error, Options, XmlOptions};
+use crate::{svgtree, Rect, Size, Error, Options, XmlOptions};
 
 mod attributes;
 mod export;
@@ -159,6 +159,28 @@ impl Tree {
     pub fn to_string(&self, opt: XmlOptions) -> String {
         export::convert(self, opt)
     }
+
+    /// Converts an SVG with a canvas cropped to the content.
+    ///
+    /// The `viewBox` is set to the content bounding box, including strokes,
+    /// and the size is adjusted to keep the content scale.
+    /// The tree itself is not modified.
+    ///
+    /// Returns `None` when the SVG has no content.
+    pub fn to_cropped_string(&self, opt: XmlOptions) -> Option<String> {
+        let bbox = self.root().calculate_bbox()?;
+        if bbox.fuzzy_eq(&Rect::new_bbox()) {
+            return None;
+        }
+
+        let svg = self.svg_node();
+        let ts = crate::utils::view_box_to_transform(svg.view_box.rect, svg.view_box.aspect, svg.size);
+        let (sx, sy) = ts.get_scale();
+        let size = Size::new(bbox.width() * sx, bbox.height() * sy)?;
+        let view_box = ViewBox { rect: bbox, aspect: svg.view_box.aspect };
+
+        Some(export::convert_with_view_box(self, size, view_box, opt))
+    }
 }
 
 /// Additional `Node` methods.
@@ -118,6 +118,21 @@ pub struct Path {
     /// `shape-rendering` in SVG.
     pub rendering_mode: ShapeRendering,
 
+    /// Vector effect.
+    ///
+    /// `vector-effect` in SVG.
+    pub vector_effect: VectorEffect,
+
+    /// Fill and stroke paint order.
+    ///
+    /// `paint-order` in SVG.
+    pub paint_order: PaintOrder,
+
+    /// Pointer events mode.
+    ///
+    /// `pointer-events` in SVG.
+    pub pointer_events: PointerEvents,
+
     /// Segments list.
     ///
     /// All segments are in absolute coordinates.
@@ -133,6 +148,9 @@ impl Default for Path {
             fill: None,
             stroke: None,
             rendering_mode: ShapeRendering::default(),
+            vector_effect: VectorEffect::default(),
+            paint_order: PaintOrder::default(),
+            pointer_events: PointerEvents::default(),
             data: Rc::new(PathData::default()),
         }
     }
@@ -526,6 +544,7 @@ pub enum FilterKind {
     FeConvolveMatrix(FeConvolveMatrix),
     FeDiffuseLighting(FeDiffuseLighting),
     FeDisplacementMap(FeDisplacementMap),
+    FeDropShadow(FeDropShadow),
     FeFlood(FeFlood),
     FeGaussianBlur(FeGaussianBlur),
     FeImage(FeImage),
@@ -548,6 +567,7 @@ impl FilterKind {
             FilterKind::FeConvolveMatrix(ref fe) => fe.input == *input,
             FilterKind::FeDiffuseLighting(ref fe) => fe.input == *input,
             FilterKind::FeDisplacementMap(ref fe) => fe.input1 == *input || fe.input2 == *input,
+            FilterKind::FeDropShadow(ref fe) => fe.input == *input,
             FilterKind::FeFlood(_) => false,
             FilterKind::FeGaussianBlur(ref fe) => fe.input == *input,
             FilterKind::FeImage(_) => false,
@@ -799,6 +819,46 @@ pub struct FeDisplacementMap {
 }
 
 
+/// A drop shadow filter primitive.
+///
+/// This is essentially `feGaussianBlur`, `feOffset` and `feFlood` joined together.
+///
+/// `feDropShadow` element in the SVG.
+#[derive(Clone, Debug)]
+pub struct FeDropShadow {
+    /// Identifies input for the given filter primitive.
+    ///
+    /// `in` in the SVG.
+    pub input: FilterInput,
+
+    /// The amount to offset the shadow along the X-axis.
+    pub dx: f64,
+
+    /// The amount to offset the shadow along the Y-axis.
+    pub dy: f64,
+
+    /// A standard deviation along the X-axis.
+    ///
+    /// `stdDeviation` in the SVG.
+    pub std_dev_x: PositiveNumber,
+
+    /// A standard deviation along the Y-axis.
+    ///
+    /// `stdDeviation` in the SVG.
+    pub std_dev_y: PositiveNumber,
+
+    /// A shadow color.
+    ///
+    /// `flood-color` in the SVG.
+    pub color: Color,
+
+    /// A shadow opacity.
+    ///
+    /// `flood-opacity` in the SVG.
+    pub opacity: Opacity,
+}
+
+
 /// A flood filter primitive.
 ///
 /// `feFlood` element in the SVG.
@@ -215,6 +215,21 @@ impl PathData {
         transform_path(&mut self[offset..], ts);
     }
 
+    /// Converts curves into line segments.
+    ///
+    /// The maximum distance between a curve and its approximation
+    /// is controlled by the `tolerance`.
+    /// Returns an unchanged copy when `tolerance` is not a positive number.
+    ///
+    /// This operation is expensive.
+    pub fn flatten(&self, tolerance: f64) -> PathData {
+        if tolerance <= 0.0 || !tolerance.is_finite() {
+            return self.clone();
+        }
+
+        flatten_path(self, tolerance)
+    }
+
     /// Returns an iterator over path subpaths.
     #[inline]
     pub fn subpaths(&self) -> SubPathIter {
@@ -573,6 +588,37 @@ fn transform_path(segments: &mut [PathSegment], ts: Transform) {
 }
 
 
+fn flatten_path(segments: &[PathSegment], tolerance: f64) -> PathData {
+    let elements = segments.iter().map(|seg| {
+        match *seg {
+            PathSegment::MoveTo { x, y } => kurbo::PathEl::MoveTo(kurbo::Point::new(x, y)),
+            PathSegment::LineTo { x, y } => kurbo::PathEl::LineTo(kurbo::Point::new(x, y)),
+            PathSegment::CurveTo { x1, y1, x2, y2, x, y } => {
+                kurbo::PathEl::CurveTo(
+                    kurbo::Point::new(x1, y1),
+                    kurbo::Point::new(x2, y2),
+                    kurbo::Point::new(x, y),
+                )
+            }
+            PathSegment::ClosePath => kurbo::PathEl::ClosePath,
+        }
+    });
+
+    let mut path = PathData::with_capacity(segments.len());
+    kurbo::flatten(elements, tolerance, |el| {
+        match el {
+            kurbo::PathEl::MoveTo(p) => path.push_move_to(p.x, p.y),
+            kurbo::PathEl::LineTo(p) => path.push_line_to(p.x, p.y),
+            kurbo::PathEl::ClosePath => path.push_close_path(),
+            // `flatten` produces only lines.
+            _ => {}
+        }
+    });
+
+    path
+}
+
+
 /// An iterator over transformed path segments.
 #[allow(missing_debug_implementations)]
 pub struct TransformedPath<'a> {
@@ -75,6 +75,9 @@ test!(fe_image_with_invalid_link);
 test!(fe_diffuse_lighting_without_light_source);
 test!(fe_specular_lighting_without_light_source);
 test!(fe_specular_lighting_with_invalid_specular_exponent);
+test!(zero_stroke_width);
+test!(fill_none);
+test!(paint_order_with_markers);
 // test!(fill_rule_on_text); // `fill-rule` cannot be set on `text`
 // test!(marker_with_visible_overflow); // Marker resolving should not produce a group.
 
@@ -130,3 +133,155 @@ test_size_err!(size_detection_err_1,
 
 test_size_err!(size_detection_err_2,
     "<svg width='0' height='0' viewBox='0 0 10 20' xmlns='http://www.w3.org/2000/svg'>");
+
+#[test]
+fn missing_text_placeholder_box() {
+    let opt = usvg::Options {
+        missing_text: usvg::MissingText::PlaceholderBox,
+        .. usvg::Options::default()
+    };
+
+    let tree = usvg::Tree::from_str(
+        "<svg viewBox='0 0 200 200' xmlns='http://www.w3.org/2000/svg'>\
+            <text x='20' y='100' font-size='40'>Text</text>\
+         </svg>", &opt).unwrap();
+
+    let out = tree.to_string(usvg::XmlOptions::default());
+    assert!(out.contains("d=\"M 20 60 L 100 60 L 100 100 L 20 100 Z\""));
+}
+
+#[test]
+fn missing_text_error() {
+    let opt = usvg::Options {
+        missing_text: usvg::MissingText::Error,
+        .. usvg::Options::default()
+    };
+
+    let res = usvg::Tree::from_str(
+        "<svg viewBox='0 0 200 200' xmlns='http://www.w3.org/2000/svg'>\
+            <text x='20' y='100'>Text</text>\
+         </svg>", &opt);
+    assert!(matches!(res, Err(usvg::Error::MissingFonts)));
+}
+
+#[test]
+fn reject_unknown_elements() {
+    let text = "<svg viewBox='0 0 200 200' xmlns='http://www.w3.org/2000/svg'>\
+                    <title>Title</title>\
+                    <foreignObject/>\
+                    <rect width='10' height='10'>\
+                        <animate/>\
+                    </rect>\
+                    <foreignObject/>\
+                </svg>";
+
+    assert!(usvg::Tree::from_str(text, &usvg::Options::default()).is_ok());
+
+    let opt = usvg::Options {
+        reject_unknown_elements: true,
+        .. usvg::Options::default()
+    };
+
+    match usvg::Tree::from_str(text, &opt) {
+        Err(usvg::Error::UnknownElements(names)) => {
+            assert_eq!(names, vec!["animate".to_string(), "foreignObject".to_string()]);
+        }
+        _ => panic!("unknown elements must be rejected"),
+    }
+}
+
+#[test]
+fn text_anchor_middle_with_word_spacing() {
+    use usvg::NodeExt;
+
+    let mut opt = usvg::Options::default();
+    opt.fontdb.load_fonts_dir("../tests/fonts");
+
+    let tree = usvg::Tree::from_str(
+        "<svg viewBox='0 0 200 200' xmlns='http://www.w3.org/2000/svg'>\
+            <text x='100' y='100' font-family='Noto Mono' font-size='48' \
+                  text-anchor='middle' word-spacing='20'>O O</text>\
+         </svg>", &opt).unwrap();
+
+    let bbox = tree.root().calculate_bbox().unwrap();
+    assert!((bbox.x() + bbox.width() / 2.0 - 100.0).abs() < 0.5);
+}
+
+#[test]
+fn to_cropped_string() {
+    let tree = usvg::Tree::from_str(
+        "<svg width='400' height='400' viewBox='0 0 200 200' xmlns='http://www.w3.org/2000/svg'>\
+            <rect x='50' y='60' width='40' height='20' fill='green' stroke='black' stroke-width='10'/>\
+         </svg>", &usvg::Options::default()).unwrap();
+
+    let out = tree.to_cropped_string(usvg::XmlOptions::default()).unwrap();
+    assert!(out.contains("width=\"100\" height=\"60\" viewBox=\"45 55 50 30\""));
+
+    let tree = usvg::Tree::from_str(
+        "<svg viewBox='0 0 200 200' xmlns='http://www.w3.org/2000/svg'/>",
+        &usvg::Options::default()).unwrap();
+    assert!(tree.to_cropped_string(usvg::XmlOptions::default()).is_none());
+}
+
+#[test]
+fn path_data_flatten() {
+    let mut path = usvg::PathData::new();
+    path.push_move_to(10.0, 10.0);
+    path.push_curve_to(10.0, 50.0, 50.0, 90.0, 90.0, 90.0);
+    path.push_close_path();
+
+    let flat = path.flatten(0.1);
+    assert!(flat.len() > 3);
+    assert!(flat.iter().all(|seg| !matches!(seg, usvg::PathSegment::CurveTo { .. })));
+    assert!(matches!(flat[0], usvg::PathSegment::MoveTo { x, y } if x == 10.0 && y == 10.0));
+    assert!(matches!(flat[flat.len() - 2], usvg::PathSegment::LineTo { x, y } if x == 90.0 && y == 90.0));
+    assert!(matches!(flat[flat.len() - 1], usvg::PathSegment::ClosePath));
+
+    // An invalid tolerance keeps curves as is.
+    for tolerance in &[0.0, -1.0, f64::NAN, f64::INFINITY] {
+        let flat = path.flatten(*tolerance);
+        assert_eq!(flat.len(), 3);
+        assert!(matches!(flat[1], usvg::PathSegment::CurveTo { .. }));
+    }
+}
+
+#[test]
+fn flatten_transforms() {
+    let text = "
+        <svg viewBox='0 0 200 200' xmlns='http://www.w3.org/2000/svg'>
+            <linearGradient id='lg1'>
+                <stop offset='0' stop-color='white'/>
+                <stop offset='1' stop-color='black'/>
+            </linearGradient>
+            <g transform='translate(10 20)' opacity='0.5'>
+                <rect id='rect1' width='10' height='10' stroke='black' transform='scale(2)'/>
+                <rect id='rect2' width='10' height='10' stroke='black' transform='scale(2 1)'/>
+                <rect id='rect3' width='10' height='10' fill='url(#lg1)'/>
+            </g>
+        </svg>";
+
+    let opt = usvg::Options {
+        flatten_transforms: true,
+        .. usvg::Options::default()
+    };
+    let tree = usvg::Tree::from_str(text, &opt).unwrap();
+
+    let path = |id: &str| match *tree.node_by_id(id).unwrap().borrow() {
+        usvg::NodeKind::Path(ref path) => path.clone(),
+        _ => unreachable!(),
+    };
+
+    let rect1 = path("rect1");
+    assert!(rect1.transform.is_default());
+    let bbox = rect1.data.bbox().unwrap();
+    assert_eq!((bbox.x(), bbox.y(), bbox.width(), bbox.height()), (10.0, 20.0, 20.0, 20.0));
+    assert_eq!(rect1.stroke.unwrap().width.value(), 2.0);
+
+    // A stroke cannot be scaled non-uniformly.
+    let rect2 = path("rect2");
+    assert_eq!(rect2.transform, usvg::Transform::new(2.0, 0.0, 0.0, 1.0, 10.0, 20.0));
+
+    // Paint servers depend on the path coordinates.
+    let rect3 = path("rect3");
+    assert_eq!(rect3.transform, usvg::Transform::new_translate(10.0, 20.0));
+}